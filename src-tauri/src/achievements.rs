@@ -292,7 +292,7 @@ pub fn check_pet_achievements(
     check_and_unlock(achievements, "wealthy", total_coins, &mut newly_unlocked);
 
     // Collector achievement
-    check_and_unlock(achievements, "collector", pet.accessories.len() as u32, &mut newly_unlocked);
+    check_and_unlock(achievements, "collector", pet.owned_accessories.len() as u32, &mut newly_unlocked);
 
     newly_unlocked
 }
@@ -401,6 +401,7 @@ mod tests {
             total_sessions: 25,
             total_focus_minutes: 625,
             total_tasks_completed: 10,
+            ..Default::default()
         };
 
         let unlocked = check_progress_achievements(&mut achievements, &progress);
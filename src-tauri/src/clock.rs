@@ -0,0 +1,177 @@
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Timelike, Utc};
+use tauri::AppHandle;
+
+use crate::models::Settings;
+
+/// Latest hour a day may start at; later values would push most of the day into yesterday.
+pub const MAX_DAY_START_HOUR: u8 = 12;
+
+/// Decides which calendar day an instant belongs to.
+///
+/// Uses the configured IANA time zone, or the system zone when none is set. Instants
+/// before `day_start_hour` still count towards the previous day.
+#[derive(Debug, Clone, Copy)]
+pub struct Clock {
+    time_zone: Option<chrono_tz::Tz>,
+    day_start_hour: u8,
+}
+
+impl Clock {
+    pub fn new(time_zone: Option<chrono_tz::Tz>, day_start_hour: u8) -> Self {
+        Self {
+            time_zone,
+            day_start_hour: day_start_hour.min(MAX_DAY_START_HOUR),
+        }
+    }
+
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self::new(
+            settings.time_zone.parse::<chrono_tz::Tz>().ok(),
+            settings.day_start_hour,
+        )
+    }
+
+    /// Falls back to the system zone and midnight when settings cannot be read.
+    pub fn load(app: &AppHandle) -> Self {
+        crate::commands::settings::get_settings(app.clone())
+            .map(|settings| Self::from_settings(&settings))
+            .unwrap_or_else(|_| Self::new(None, 0))
+    }
+
+    fn localize(&self, instant: DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self.time_zone {
+            Some(tz) => instant.with_timezone(&tz).fixed_offset(),
+            None => instant.with_timezone(&chrono::Local).fixed_offset(),
+        }
+    }
+
+    pub fn day_of(&self, instant: DateTime<Utc>) -> NaiveDate {
+        let local = self.localize(instant).naive_local();
+        (local - chrono::Duration::hours(i64::from(self.day_start_hour))).date()
+    }
+
+    /// Day of an RFC 3339 timestamp, formatted as `YYYY-MM-DD`.
+    pub fn day_of_timestamp(&self, timestamp: &str) -> Option<String> {
+        let instant = DateTime::parse_from_rfc3339(timestamp).ok()?;
+        Some(format_day(self.day_of(instant.with_timezone(&Utc))))
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.day_of(Utc::now())
+    }
+
+    /// Wall-clock hour in the configured zone, ignoring the day start.
+    pub fn hour_of(&self, instant: DateTime<Utc>) -> u32 {
+        self.localize(instant).hour()
+    }
+
+    /// Wall-clock weekday (0 = Monday) and hour of an RFC 3339 timestamp.
+    pub fn weekday_hour_of_timestamp(&self, timestamp: &str) -> Option<(u32, u32)> {
+        let instant = DateTime::parse_from_rfc3339(timestamp).ok()?;
+        let local = self.localize(instant.with_timezone(&Utc));
+        Some((local.weekday().num_days_from_monday(), local.hour()))
+    }
+}
+
+fn format_day(day: NaiveDate) -> String {
+    day.format("%Y-%m-%d").to_string()
+}
+
+/// Returns `time_zone` when it names a known IANA zone, otherwise "" for the system zone.
+pub fn normalize_time_zone(time_zone: &str) -> String {
+    time_zone
+        .trim()
+        .parse::<chrono_tz::Tz>()
+        .map(|tz| tz.name().to_string())
+        .unwrap_or_default()
+}
+
+pub fn today(app: &AppHandle) -> String {
+    format_day(Clock::load(app).today())
+}
+
+pub fn today_date(app: &AppHandle) -> NaiveDate {
+    Clock::load(app).today()
+}
+
+pub fn day_of_timestamp(app: &AppHandle, timestamp: &str) -> Option<String> {
+    Clock::load(app).day_of_timestamp(timestamp)
+}
+
+pub fn local_hour(app: &AppHandle) -> u32 {
+    Clock::load(app).hour_of(Utc::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instant(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn day(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn day_follows_configured_time_zone() {
+        let tokyo = Clock::new(Some(chrono_tz::Asia::Tokyo), 0);
+        let los_angeles = Clock::new(Some(chrono_tz::America::Los_Angeles), 0);
+        let moment = instant("2026-03-10T20:00:00Z");
+        assert_eq!(tokyo.day_of(moment), day("2026-03-11"));
+        assert_eq!(los_angeles.day_of(moment), day("2026-03-10"));
+        assert_eq!(tokyo.hour_of(moment), 5);
+    }
+
+    #[test]
+    fn day_start_hour_keeps_late_night_on_previous_day() {
+        let night_owl = Clock::new(Some(chrono_tz::UTC), 4);
+        assert_eq!(
+            night_owl.day_of(instant("2026-03-11T03:59:00Z")),
+            day("2026-03-10")
+        );
+        assert_eq!(
+            night_owl.day_of(instant("2026-03-11T04:00:00Z")),
+            day("2026-03-11")
+        );
+        assert_eq!(Clock::new(None, 30).day_start_hour, MAX_DAY_START_HOUR);
+    }
+
+    #[test]
+    fn day_of_timestamp_converts_utc_timestamps() {
+        let berlin = Clock::new(Some(chrono_tz::Europe::Berlin), 0);
+        assert_eq!(
+            berlin.day_of_timestamp("2026-01-01T23:30:00Z").as_deref(),
+            Some("2026-01-02")
+        );
+        assert_eq!(berlin.day_of_timestamp("not a timestamp"), None);
+    }
+
+    #[test]
+    fn weekday_hour_uses_wall_clock_time() {
+        let tokyo = Clock::new(Some(chrono_tz::Asia::Tokyo), 4);
+        // Sunday 20:30 UTC is Monday 05:30 in Tokyo.
+        assert_eq!(
+            tokyo.weekday_hour_of_timestamp("2026-03-08T20:30:00Z"),
+            Some((0, 5))
+        );
+        assert_eq!(tokyo.weekday_hour_of_timestamp("garbage"), None);
+    }
+
+    #[test]
+    fn normalize_time_zone_accepts_only_known_zones() {
+        assert_eq!(normalize_time_zone(" Europe/Berlin "), "Europe/Berlin");
+        assert_eq!(normalize_time_zone("Mars/Olympus"), "");
+        assert_eq!(normalize_time_zone(""), "");
+    }
+
+    #[test]
+    fn today_returns_valid_date_format() {
+        let today = format_day(Clock::new(None, 0).today());
+        assert_eq!(today.len(), 10);
+        assert!(NaiveDate::parse_from_str(&today, "%Y-%m-%d").is_ok());
+    }
+}
@@ -0,0 +1,351 @@
+use std::cmp::Reverse;
+
+use serde_json::json;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use crate::clock::Clock;
+use crate::commands::period_goals::period_bounds;
+use crate::models::{AnalyticsReport, DailySummary, PomodoroSession, SummaryRollup};
+use crate::progression::parse_date;
+
+const SUMMARY_ROLLUPS_KEY: &str = "summary_rollups";
+const ROLLUP_PERIODS: &[&str] = &["week", "month"];
+const MAX_WEEKLY_ROLLUPS: usize = 520;
+const MAX_MONTHLY_ROLLUPS: usize = 120;
+const DEFAULT_REPORT_PERIODS: u32 = 12;
+const DAYS_PER_WEEK: usize = 7;
+const HOURS_PER_DAY: usize = 24;
+
+fn add_summary(rollup: &mut SummaryRollup, summary: &DailySummary) {
+    if summary.sessions_completed > 0 || summary.tasks_completed > 0 {
+        rollup.active_days += 1;
+    }
+    rollup.sessions_completed += summary.sessions_completed;
+    rollup.focus_minutes += summary.focus_minutes;
+    rollup.tasks_completed += summary.tasks_completed;
+    rollup.goals_completed += summary.goals_completed;
+    rollup.coins_earned += summary.coins_earned;
+    rollup.xp_earned += summary.xp_earned;
+    rollup.breaks_taken += summary.breaks_taken;
+}
+
+/// Adds each summary into the rollups of its week and month.
+fn fold_summaries(
+    rollups: &mut Vec<SummaryRollup>,
+    summaries: &[DailySummary],
+    week_start_day: u8,
+) {
+    for summary in summaries {
+        let Some(date) = parse_date(&summary.date) else {
+            continue;
+        };
+        for period in ROLLUP_PERIODS {
+            let (start, end) = period_bounds(period, date, week_start_day);
+            let start = start.format("%Y-%m-%d").to_string();
+            let idx = rollups
+                .iter()
+                .position(|rollup| rollup.period == *period && rollup.start == start)
+                .unwrap_or_else(|| {
+                    rollups.push(SummaryRollup {
+                        period: period.to_string(),
+                        start,
+                        end: end.format("%Y-%m-%d").to_string(),
+                        ..Default::default()
+                    });
+                    rollups.len() - 1
+                });
+            add_summary(&mut rollups[idx], summary);
+        }
+    }
+}
+
+/// Keeps known periods only, newest first, capped per period.
+pub(crate) fn sanitize_summary_rollups(rollups: &mut Vec<SummaryRollup>) {
+    rollups.retain(|rollup| {
+        ROLLUP_PERIODS.contains(&rollup.period.as_str()) && parse_date(&rollup.start).is_some()
+    });
+    rollups.sort_by(|a, b| a.period.cmp(&b.period).then(b.start.cmp(&a.start)));
+    rollups.dedup_by(|a, b| a.period == b.period && a.start == b.start);
+
+    let mut weeks = 0;
+    let mut months = 0;
+    rollups.retain(|rollup| {
+        let (count, max) = if rollup.period == "week" {
+            (&mut weeks, MAX_WEEKLY_ROLLUPS)
+        } else {
+            (&mut months, MAX_MONTHLY_ROLLUPS)
+        };
+        *count += 1;
+        *count <= max
+    });
+}
+
+fn load_summary_rollups(app: &AppHandle) -> Result<Vec<SummaryRollup>, String> {
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    Ok(store
+        .get(SUMMARY_ROLLUPS_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+/// Folds daily summaries that no longer fit in storage into the long-term rollups.
+pub(crate) fn archive_summaries(app: &AppHandle, aged_out: &[DailySummary]) -> Result<(), String> {
+    if aged_out.is_empty() {
+        return Ok(());
+    }
+    let settings = crate::commands::settings::get_settings(app.clone())?;
+    let mut rollups = load_summary_rollups(app)?;
+    fold_summaries(&mut rollups, aged_out, settings.week_start_day);
+    sanitize_summary_rollups(&mut rollups);
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    store.set(SUMMARY_ROLLUPS_KEY, json!(rollups));
+    Ok(())
+}
+
+/// Archived rollups plus the stored daily summaries for one period, newest first.
+fn rollups_for(
+    period: &str,
+    archived: &[SummaryRollup],
+    summaries: &[DailySummary],
+    week_start_day: u8,
+    limit: usize,
+) -> Vec<SummaryRollup> {
+    let mut rollups = archived.to_vec();
+    fold_summaries(&mut rollups, summaries, week_start_day);
+    rollups.retain(|rollup| rollup.period == period);
+    rollups.sort_by(|a, b| b.start.cmp(&a.start));
+    rollups.truncate(limit);
+    rollups
+}
+
+/// Focus minutes of completed sessions by local weekday and start hour.
+fn focus_heatmap(sessions: &[PomodoroSession], clock: &Clock) -> Vec<Vec<u32>> {
+    let mut heatmap = vec![vec![0; HOURS_PER_DAY]; DAYS_PER_WEEK];
+    for session in sessions.iter().filter(|s| s.completed_at.is_some()) {
+        if let Some((weekday, hour)) = clock.weekday_hour_of_timestamp(&session.started_at) {
+            heatmap[weekday as usize][hour as usize] += session.work_duration / 60;
+        }
+    }
+    heatmap
+}
+
+/// Weekdays with the most and the fewest focus minutes; ties go to the earlier weekday.
+fn weekday_extremes(heatmap: &[Vec<u32>]) -> (Option<u8>, Option<u8>) {
+    let totals: Vec<u32> = heatmap.iter().map(|row| row.iter().sum()).collect();
+    if totals.iter().all(|total| *total == 0) {
+        return (None, None);
+    }
+    let best = totals
+        .iter()
+        .enumerate()
+        .max_by_key(|(weekday, total)| (**total, Reverse(*weekday)))
+        .map(|(weekday, _)| weekday as u8);
+    let worst = totals
+        .iter()
+        .enumerate()
+        .min_by_key(|(weekday, total)| (**total, *weekday))
+        .map(|(weekday, _)| weekday as u8);
+    (best, worst)
+}
+
+/// Average completed session length in minutes and the share of sessions completed.
+fn session_stats(sessions: &[PomodoroSession]) -> (f32, f32) {
+    if sessions.is_empty() {
+        return (0.0, 0.0);
+    }
+    let completed: Vec<&PomodoroSession> = sessions
+        .iter()
+        .filter(|session| session.completed_at.is_some())
+        .collect();
+    let average_minutes = if completed.is_empty() {
+        0.0
+    } else {
+        let total_secs: u64 = completed
+            .iter()
+            .map(|session| u64::from(session.work_duration))
+            .sum();
+        total_secs as f32 / 60.0 / completed.len() as f32
+    };
+    let completion_rate = completed.len() as f32 / sessions.len() as f32;
+    (average_minutes, completion_rate)
+}
+
+/// Returns weekly and monthly rollups plus focus patterns across all recorded sessions.
+#[tauri::command]
+pub fn get_analytics_report(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    weeks: Option<u32>,
+    months: Option<u32>,
+) -> Result<AnalyticsReport, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    let summaries: Vec<DailySummary> = store
+        .get("daily_summaries")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    let sessions: Vec<PomodoroSession> = store
+        .get("sessions")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    let archived = load_summary_rollups(&app)?;
+    let settings = crate::commands::settings::get_settings(app.clone())?;
+    let clock = Clock::from_settings(&settings);
+
+    let weeks = weeks
+        .unwrap_or(DEFAULT_REPORT_PERIODS)
+        .clamp(1, MAX_WEEKLY_ROLLUPS as u32) as usize;
+    let months = months
+        .unwrap_or(DEFAULT_REPORT_PERIODS)
+        .clamp(1, MAX_MONTHLY_ROLLUPS as u32) as usize;
+    let focus_heatmap = focus_heatmap(&sessions, &clock);
+    let (best_weekday, worst_weekday) = weekday_extremes(&focus_heatmap);
+    let (average_session_minutes, completion_rate) = session_stats(&sessions);
+
+    Ok(AnalyticsReport {
+        weekly: rollups_for(
+            "week",
+            &archived,
+            &summaries,
+            settings.week_start_day,
+            weeks,
+        ),
+        monthly: rollups_for(
+            "month",
+            &archived,
+            &summaries,
+            settings.week_start_day,
+            months,
+        ),
+        focus_heatmap,
+        best_weekday,
+        worst_weekday,
+        average_session_minutes,
+        completion_rate,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(date: &str, sessions: u32, minutes: u32) -> DailySummary {
+        DailySummary {
+            date: date.to_string(),
+            sessions_completed: sessions,
+            focus_minutes: minutes,
+            ..Default::default()
+        }
+    }
+
+    fn session(started_at: &str, completed: bool) -> PomodoroSession {
+        PomodoroSession {
+            id: started_at.to_string(),
+            started_at: started_at.to_string(),
+            completed_at: completed.then(|| started_at.to_string()),
+            work_duration: 1500,
+            break_duration: 300,
+            break_completed_at: None,
+        }
+    }
+
+    #[test]
+    fn fold_summaries_groups_days_by_week_and_month() {
+        let mut rollups = Vec::new();
+        // 2026-03-01 is a Sunday, so it closes the week starting Monday 2026-02-23.
+        let days = vec![
+            summary("2026-03-01", 2, 50),
+            summary("2026-03-02", 1, 25),
+            summary("2026-03-03", 0, 0),
+        ];
+        fold_summaries(&mut rollups, &days, 0);
+
+        let weeks = rollups_for("week", &rollups, &[], 0, 10);
+        assert_eq!(weeks.len(), 2);
+        assert_eq!(weeks[0].start, "2026-03-02");
+        assert_eq!(weeks[0].end, "2026-03-08");
+        assert_eq!(weeks[0].active_days, 1);
+        assert_eq!(weeks[1].start, "2026-02-23");
+        assert_eq!(weeks[1].focus_minutes, 50);
+
+        let months = rollups_for("month", &rollups, &[], 0, 10);
+        assert_eq!(months.len(), 1);
+        assert_eq!(months[0].start, "2026-03-01");
+        assert_eq!(months[0].end, "2026-03-31");
+        assert_eq!(months[0].sessions_completed, 3);
+    }
+
+    #[test]
+    fn rollups_merge_archived_and_stored_days() {
+        let mut archived = Vec::new();
+        fold_summaries(&mut archived, &[summary("2026-03-02", 1, 25)], 0);
+        let weeks = rollups_for("week", &archived, &[summary("2026-03-04", 2, 50)], 0, 10);
+        assert_eq!(weeks.len(), 1);
+        assert_eq!(weeks[0].sessions_completed, 3);
+        assert_eq!(weeks[0].active_days, 2);
+    }
+
+    #[test]
+    fn sanitize_summary_rollups_drops_unknown_and_caps() {
+        let mut rollups: Vec<SummaryRollup> = (0..MAX_MONTHLY_ROLLUPS + 5)
+            .map(|i| SummaryRollup {
+                period: "month".to_string(),
+                start: (chrono::NaiveDate::from_ymd_opt(2000, 1, 1).unwrap()
+                    + chrono::Months::new(i as u32))
+                .format("%Y-%m-%d")
+                .to_string(),
+                ..Default::default()
+            })
+            .collect();
+        rollups.push(SummaryRollup {
+            period: "year".to_string(),
+            start: "2026-01-01".to_string(),
+            ..Default::default()
+        });
+        sanitize_summary_rollups(&mut rollups);
+        assert_eq!(rollups.len(), MAX_MONTHLY_ROLLUPS);
+        assert!(rollups.iter().all(|rollup| rollup.period == "month"));
+        assert!(rollups[0].start > rollups[1].start);
+    }
+
+    #[test]
+    fn heatmap_buckets_completed_sessions_by_local_weekday_and_hour() {
+        let clock = Clock::new(Some(chrono_tz::UTC), 0);
+        // 2026-03-02 is a Monday, 2026-03-04 a Wednesday.
+        let sessions = vec![
+            session("2026-03-02T09:10:00Z", true),
+            session("2026-03-02T09:40:00Z", true),
+            session("2026-03-04T22:00:00Z", true),
+            session("2026-03-05T08:00:00Z", false),
+        ];
+        let heatmap = focus_heatmap(&sessions, &clock);
+        assert_eq!(heatmap.len(), DAYS_PER_WEEK);
+        assert_eq!(heatmap[0][9], 50);
+        assert_eq!(heatmap[2][22], 25);
+        assert_eq!(heatmap[3][8], 0);
+        assert_eq!(weekday_extremes(&heatmap), (Some(0), Some(1)));
+    }
+
+    #[test]
+    fn weekday_extremes_are_empty_without_focus() {
+        let heatmap = vec![vec![0; HOURS_PER_DAY]; DAYS_PER_WEEK];
+        assert_eq!(weekday_extremes(&heatmap), (None, None));
+    }
+
+    #[test]
+    fn session_stats_average_completed_sessions() {
+        let mut long = session("2026-03-02T09:00:00Z", true);
+        long.work_duration = 3000;
+        let sessions = vec![
+            session("2026-03-02T08:00:00Z", true),
+            long,
+            session("2026-03-02T10:00:00Z", false),
+            session("2026-03-02T11:00:00Z", false),
+        ];
+        let (average, rate) = session_stats(&sessions);
+        assert!((average - 37.5).abs() < f32::EPSILON);
+        assert!((rate - 0.5).abs() < f32::EPSILON);
+        assert_eq!(session_stats(&[]), (0.0, 0.0));
+    }
+}
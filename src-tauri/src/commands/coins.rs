@@ -3,37 +3,487 @@ use tauri::{AppHandle, Emitter};
 use tauri_plugin_store::StoreExt;
 
 use crate::events::EVENT_COINS_CHANGED;
-use crate::models::CoinBalance;
+use crate::models::{CoinBalance, CoinIntegrityReport, CoinTransaction};
 
-#[tauri::command]
-pub fn get_coin_balance(app: AppHandle) -> Result<CoinBalance, String> {
+const COIN_TRANSACTIONS_KEY: &str = "coin_transactions";
+const DEFAULT_TRANSACTION_PAGE: u32 = 50;
+const MAX_TRANSACTION_PAGE: u32 = 1_000;
+/// How long after buying a shop item it can still be returned.
+const REFUND_WINDOW_MINUTES: i64 = 15;
+/// Ledger length past which the oldest entries are folded into the opening balance.
+const MAX_COIN_TRANSACTIONS: usize = 5_000;
+
+pub const DIRECTION_CREDIT: &str = "credit";
+pub const DIRECTION_DEBIT: &str = "debit";
+
+pub const COIN_SOURCE_POMODORO: &str = "pomodoro";
+pub const COIN_SOURCE_QUEST: &str = "quest";
+pub const COIN_SOURCE_GOAL: &str = "goal";
+pub const COIN_SOURCE_PERFECT_DAY: &str = "perfect_day";
+pub const COIN_SOURCE_LEVEL: &str = "level";
+pub const COIN_SOURCE_PURCHASE: &str = "purchase";
+pub const COIN_SOURCE_STREAK_FREEZE: &str = "streak_freeze";
+pub const COIN_SOURCE_QUEST_REROLL: &str = "quest_reroll";
+/// Credits that return the coins of a refunded purchase; they lower `spent`, not raise `total`.
+pub const COIN_SOURCE_REFUND: &str = "refund";
+/// Manual corrections and the opening balance carried over from before the ledger.
+pub const COIN_SOURCE_ADMIN: &str = "admin";
+pub const OPENING_BALANCE_REFERENCE: &str = "opening_balance";
+fn load_balance(app: &AppHandle) -> Result<CoinBalance, String> {
     let store = app.store("store.json").map_err(|e| e.to_string())?;
-    let coins: CoinBalance = store
+    Ok(store
         .get("coins")
         .and_then(|v| serde_json::from_value(v).ok())
-        .unwrap_or_default();
-    Ok(coins)
+        .unwrap_or_default())
 }
 
-#[tauri::command]
-pub fn spend_coins(
-    app: AppHandle,
-    store_lock: tauri::State<'_, crate::StoreLock>,
+fn transaction(
+    amount: u32,
+    direction: &str,
+    source: &str,
+    reference_id: Option<&str>,
+) -> CoinTransaction {
+    CoinTransaction {
+        id: uuid::Uuid::new_v4().to_string(),
+        amount,
+        direction: direction.to_string(),
+        source: source.to_string(),
+        reference_id: reference_id.map(str::to_string),
+        created_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+/// Entries that reproduce a balance earned before transactions were recorded.
+fn opening_transactions(balance: &CoinBalance) -> Vec<CoinTransaction> {
+    [
+        (balance.total, DIRECTION_CREDIT),
+        (balance.spent, DIRECTION_DEBIT),
+    ]
+    .into_iter()
+    .filter(|(amount, _)| *amount > 0)
+    .map(|(amount, direction)| {
+        transaction(
+            amount,
+            direction,
+            COIN_SOURCE_ADMIN,
+            Some(OPENING_BALANCE_REFERENCE),
+        )
+    })
+    .collect()
+}
+
+/// Loads the ledger, seeding it from the balance the first time it is read.
+fn load_transactions(app: &AppHandle) -> Result<Vec<CoinTransaction>, String> {
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    if let Some(transactions) = store
+        .get(COIN_TRANSACTIONS_KEY)
+        .and_then(|v| serde_json::from_value::<Vec<CoinTransaction>>(v).ok())
+    {
+        return Ok(transactions);
+    }
+    let transactions = opening_transactions(&load_balance(app)?);
+    store.set(COIN_TRANSACTIONS_KEY, json!(transactions));
+    Ok(transactions)
+}
+
+/// Folds the oldest entries into a fresh pair of opening-balance entries once the ledger
+/// outgrows `MAX_COIN_TRANSACTIONS`, keeping its totals. Entries still inside the refund
+/// window at `now` are never folded.
+fn compact_transactions(
+    transactions: &mut Vec<CoinTransaction>,
+    now: chrono::DateTime<chrono::Utc>,
+) {
+    let Some(overflow) = transactions.len().checked_sub(MAX_COIN_TRANSACTIONS) else {
+        return;
+    };
+    let cutoff = now - chrono::Duration::minutes(REFUND_WINDOW_MINUTES);
+    let foldable = transactions
+        .iter()
+        .take_while(|entry| {
+            chrono::DateTime::parse_from_rfc3339(&entry.created_at)
+                .is_ok_and(|created_at| created_at < cutoff)
+        })
+        .count();
+    // Two opening entries replace the folded ones, so fold two more than the overflow.
+    let fold = (overflow + 2).min(foldable);
+    if fold <= 2 {
+        return;
+    }
+    let folded: Vec<CoinTransaction> = transactions.drain(..fold).collect();
+    let (credits, debits) = ledger_totals(&folded);
+    let folded_until = folded[fold - 1].created_at.clone();
+    let opening = opening_transactions(&CoinBalance {
+        total: u32::try_from(credits).unwrap_or(u32::MAX),
+        spent: u32::try_from(debits).unwrap_or(u32::MAX),
+    })
+    .into_iter()
+    .map(|entry| CoinTransaction {
+        created_at: folded_until.clone(),
+        ..entry
+    });
+    transactions.splice(..0, opening);
+}
+
+fn append_transaction(app: &AppHandle, entry: CoinTransaction) -> Result<(), String> {
+    let mut transactions = load_transactions(app)?;
+    transactions.push(entry);
+    compact_transactions(&mut transactions, chrono::Utc::now());
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    store.set(COIN_TRANSACTIONS_KEY, json!(transactions));
+    Ok(())
+}
+
+/// Adds earned coins to the balance, records why and notifies listeners.
+pub(crate) fn credit_coins(
+    app: &AppHandle,
     amount: u32,
+    source: &str,
+    reference_id: Option<&str>,
 ) -> Result<CoinBalance, String> {
-    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let mut coins = load_balance(app)?;
+    append_transaction(
+        app,
+        transaction(amount, DIRECTION_CREDIT, source, reference_id),
+    )?;
+    coins.total += amount;
     let store = app.store("store.json").map_err(|e| e.to_string())?;
-    let mut coins: CoinBalance = store
-        .get("coins")
-        .and_then(|v| serde_json::from_value(v).ok())
-        .unwrap_or_default();
+    store.set("coins", json!(coins));
+    let _ = app.emit(EVENT_COINS_CHANGED, &coins);
+    Ok(coins)
+}
 
+/// Spends coins if the balance allows it, records why and notifies listeners.
+pub(crate) fn debit_coins(
+    app: &AppHandle,
+    amount: u32,
+    source: &str,
+    reference_id: Option<&str>,
+) -> Result<CoinBalance, String> {
+    let mut coins = load_balance(app)?;
     if coins.available() < amount {
         return Err("Insufficient coins".to_string());
     }
-
+    append_transaction(
+        app,
+        transaction(amount, DIRECTION_DEBIT, source, reference_id),
+    )?;
     coins.spent += amount;
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    store.set("coins", json!(coins));
+    let _ = app.emit(EVENT_COINS_CHANGED, &coins);
+    Ok(coins)
+}
+
+/// Returns coins taken by a purchase and records the refund against it.
+fn refund_coins(app: &AppHandle, purchase: &CoinTransaction) -> Result<CoinBalance, String> {
+    let mut coins = load_balance(app)?;
+    append_transaction(
+        app,
+        transaction(
+            purchase.amount,
+            DIRECTION_CREDIT,
+            COIN_SOURCE_REFUND,
+            Some(&purchase.id),
+        ),
+    )?;
+    coins.spent = coins.spent.saturating_sub(purchase.amount);
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
     store.set("coins", json!(coins));
     let _ = app.emit(EVENT_COINS_CHANGED, &coins);
     Ok(coins)
 }
+
+/// Finds the purchase behind `transaction_id` if it may still be refunded at `now`.
+fn refundable_purchase(
+    transactions: &[CoinTransaction],
+    transaction_id: &str,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<CoinTransaction, String> {
+    let purchase = transactions
+        .iter()
+        .find(|entry| entry.id == transaction_id)
+        .ok_or_else(|| "Transaction not found".to_string())?;
+    if purchase.direction != DIRECTION_DEBIT || purchase.source != COIN_SOURCE_PURCHASE {
+        return Err("Only shop purchases can be refunded".to_string());
+    }
+    let already_refunded = transactions.iter().any(|entry| {
+        entry.source == COIN_SOURCE_REFUND && entry.reference_id.as_deref() == Some(transaction_id)
+    });
+    if already_refunded {
+        return Err("Purchase already refunded".to_string());
+    }
+    let purchased_at = chrono::DateTime::parse_from_rfc3339(&purchase.created_at)
+        .map_err(|e| e.to_string())?
+        .with_timezone(&chrono::Utc);
+    if now.signed_duration_since(purchased_at) > chrono::Duration::minutes(REFUND_WINDOW_MINUTES) {
+        return Err(format!(
+            "Purchases can only be refunded within {} minutes",
+            REFUND_WINDOW_MINUTES
+        ));
+    }
+    Ok(purchase.clone())
+}
+
+/// Validates a refund of `transaction_id`, then calls `undo` with the purchase before
+/// returning the coins, so nothing is credited when the purchase cannot be undone.
+pub(crate) fn refund_purchase_with<F>(
+    app: &AppHandle,
+    transaction_id: &str,
+    undo: F,
+) -> Result<CoinBalance, String>
+where
+    F: FnOnce(&CoinTransaction) -> Result<(), String>,
+{
+    let purchase =
+        refundable_purchase(&load_transactions(app)?, transaction_id, chrono::Utc::now())?;
+    undo(&purchase)?;
+    refund_coins(app, &purchase)
+}
+
+/// Sums earned credits and net debits; refunds count against debits, matching how they
+/// lower `spent`. Unknown directions are ignored.
+fn ledger_totals(transactions: &[CoinTransaction]) -> (u64, u64) {
+    let (credits, debits, refunds) =
+        transactions
+            .iter()
+            .fold((0u64, 0u64, 0u64), |(credits, debits, refunds), entry| {
+                let amount = u64::from(entry.amount);
+                match entry.direction.as_str() {
+                    DIRECTION_CREDIT if entry.source == COIN_SOURCE_REFUND => {
+                        (credits, debits, refunds + amount)
+                    }
+                    DIRECTION_CREDIT => (credits + amount, debits, refunds),
+                    DIRECTION_DEBIT => (credits, debits + amount, refunds),
+                    _ => (credits, debits, refunds),
+                }
+            });
+    (credits, debits.saturating_sub(refunds))
+}
+
+fn integrity_report(
+    balance: &CoinBalance,
+    transactions: &[CoinTransaction],
+) -> CoinIntegrityReport {
+    let (ledger_credits, ledger_debits) = ledger_totals(transactions);
+    CoinIntegrityReport {
+        balance_total: balance.total,
+        balance_spent: balance.spent,
+        ledger_credits,
+        ledger_debits,
+        consistent: ledger_credits == u64::from(balance.total)
+            && ledger_debits == u64::from(balance.spent),
+    }
+}
+
+/// Newest first, optionally limited to one source.
+fn page_transactions(
+    mut transactions: Vec<CoinTransaction>,
+    source: Option<&str>,
+    limit: usize,
+) -> Vec<CoinTransaction> {
+    if let Some(source) = source {
+        transactions.retain(|entry| entry.source == source);
+    }
+    transactions.reverse();
+    transactions.truncate(limit);
+    transactions
+}
+
+#[tauri::command]
+pub fn get_coin_balance(app: AppHandle) -> Result<CoinBalance, String> {
+    load_balance(&app)
+}
+
+/// Returns up to `limit` coin transactions, newest first.
+#[tauri::command]
+pub fn get_coin_transactions(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    limit: Option<u32>,
+    source: Option<String>,
+) -> Result<Vec<CoinTransaction>, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let limit = limit
+        .unwrap_or(DEFAULT_TRANSACTION_PAGE)
+        .clamp(1, MAX_TRANSACTION_PAGE) as usize;
+    Ok(page_transactions(
+        load_transactions(&app)?,
+        source.as_deref(),
+        limit,
+    ))
+}
+
+/// Checks that the stored balance equals the sum of the transaction ledger.
+#[tauri::command]
+pub fn check_coin_integrity(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+) -> Result<CoinIntegrityReport, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let transactions = load_transactions(&app)?;
+    Ok(integrity_report(&load_balance(&app)?, &transactions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opening_transactions_reproduce_existing_balance() {
+        let balance = CoinBalance {
+            total: 120,
+            spent: 45,
+        };
+        let transactions = opening_transactions(&balance);
+        assert_eq!(transactions.len(), 2);
+        assert!(integrity_report(&balance, &transactions).consistent);
+        assert!(opening_transactions(&CoinBalance::default()).is_empty());
+    }
+
+    #[test]
+    fn integrity_report_flags_drift() {
+        let transactions = vec![
+            transaction(10, DIRECTION_CREDIT, COIN_SOURCE_POMODORO, Some("s1")),
+            transaction(30, DIRECTION_CREDIT, COIN_SOURCE_QUEST, Some("q1")),
+            transaction(25, DIRECTION_DEBIT, COIN_SOURCE_PURCHASE, Some("scarf")),
+        ];
+        let balanced = CoinBalance {
+            total: 40,
+            spent: 25,
+        };
+        assert!(integrity_report(&balanced, &transactions).consistent);
+
+        let drifted = CoinBalance {
+            total: 50,
+            spent: 25,
+        };
+        let report = integrity_report(&drifted, &transactions);
+        assert!(!report.consistent);
+        assert_eq!(report.ledger_credits, 40);
+        assert_eq!(report.ledger_debits, 25);
+    }
+
+    #[test]
+    fn refunds_lower_net_debits() {
+        let purchase = transaction(25, DIRECTION_DEBIT, COIN_SOURCE_PURCHASE, Some("scarf"));
+        let transactions = vec![
+            transaction(40, DIRECTION_CREDIT, COIN_SOURCE_POMODORO, Some("s1")),
+            purchase.clone(),
+            transaction(25, DIRECTION_CREDIT, COIN_SOURCE_REFUND, Some(&purchase.id)),
+        ];
+        let balance = CoinBalance {
+            total: 40,
+            spent: 0,
+        };
+        assert!(integrity_report(&balance, &transactions).consistent);
+    }
+
+    #[test]
+    fn refundable_purchase_enforces_kind_window_and_single_refund() {
+        let purchase = transaction(25, DIRECTION_DEBIT, COIN_SOURCE_PURCHASE, Some("scarf"));
+        let freeze = transaction(50, DIRECTION_DEBIT, COIN_SOURCE_STREAK_FREEZE, Some("x"));
+        let purchased_at = chrono::DateTime::parse_from_rfc3339(&purchase.created_at)
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let mut transactions = vec![purchase.clone(), freeze.clone()];
+
+        assert_eq!(
+            refundable_purchase(&transactions, &purchase.id, purchased_at)
+                .unwrap()
+                .reference_id
+                .as_deref(),
+            Some("scarf")
+        );
+        assert!(refundable_purchase(&transactions, &freeze.id, purchased_at).is_err());
+        assert!(refundable_purchase(&transactions, "missing", purchased_at).is_err());
+        let late = purchased_at + chrono::Duration::minutes(REFUND_WINDOW_MINUTES + 1);
+        assert!(refundable_purchase(&transactions, &purchase.id, late).is_err());
+
+        transactions.push(transaction(
+            25,
+            DIRECTION_CREDIT,
+            COIN_SOURCE_REFUND,
+            Some(&purchase.id),
+        ));
+        assert!(refundable_purchase(&transactions, &purchase.id, purchased_at).is_err());
+    }
+
+    fn transaction_at(
+        amount: u32,
+        direction: &str,
+        source: &str,
+        reference_id: Option<&str>,
+        created_at: &str,
+    ) -> CoinTransaction {
+        CoinTransaction {
+            created_at: created_at.to_string(),
+            ..transaction(amount, direction, source, reference_id)
+        }
+    }
+
+    #[test]
+    fn compact_transactions_folds_old_entries_and_keeps_totals() {
+        let old = "2026-01-01T00:00:00+00:00";
+        let now = chrono::DateTime::parse_from_rfc3339("2026-01-02T00:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let old_purchase = transaction_at(5, DIRECTION_DEBIT, COIN_SOURCE_PURCHASE, None, old);
+        let mut transactions: Vec<CoinTransaction> = (0..MAX_COIN_TRANSACTIONS)
+            .map(|_| transaction_at(2, DIRECTION_CREDIT, COIN_SOURCE_POMODORO, None, old))
+            .collect();
+        transactions[1] = old_purchase.clone();
+        transactions.push(transaction_at(
+            5,
+            DIRECTION_CREDIT,
+            COIN_SOURCE_REFUND,
+            Some(&old_purchase.id),
+            old,
+        ));
+        let recent = transaction(7, DIRECTION_DEBIT, COIN_SOURCE_PURCHASE, Some("scarf"));
+        transactions.push(CoinTransaction {
+            created_at: now.to_rfc3339(),
+            ..recent.clone()
+        });
+        let balance = CoinBalance {
+            total: 2 * (MAX_COIN_TRANSACTIONS as u32 - 1),
+            spent: 7,
+        };
+        assert!(integrity_report(&balance, &transactions).consistent);
+
+        compact_transactions(&mut transactions, now);
+        assert_eq!(transactions.len(), MAX_COIN_TRANSACTIONS);
+        assert_eq!(
+            transactions[0].reference_id.as_deref(),
+            Some(OPENING_BALANCE_REFERENCE)
+        );
+        assert_eq!(transactions[0].created_at, old);
+        assert!(integrity_report(&balance, &transactions).consistent);
+        assert!(refundable_purchase(&transactions, &recent.id, now).is_ok());
+
+        compact_transactions(&mut transactions, now);
+        assert_eq!(transactions.len(), MAX_COIN_TRANSACTIONS);
+    }
+
+    #[test]
+    fn compact_transactions_never_folds_refundable_purchases() {
+        let now = chrono::Utc::now();
+        let mut transactions: Vec<CoinTransaction> = (0..=MAX_COIN_TRANSACTIONS)
+            .map(|_| transaction(1, DIRECTION_DEBIT, COIN_SOURCE_PURCHASE, None))
+            .collect();
+        compact_transactions(&mut transactions, now);
+        assert_eq!(transactions.len(), MAX_COIN_TRANSACTIONS + 1);
+    }
+
+    #[test]
+    fn page_transactions_filters_and_orders_newest_first() {
+        let transactions = vec![
+            transaction(10, DIRECTION_CREDIT, COIN_SOURCE_POMODORO, Some("s1")),
+            transaction(5, DIRECTION_DEBIT, COIN_SOURCE_PURCHASE, Some("apple")),
+            transaction(10, DIRECTION_CREDIT, COIN_SOURCE_POMODORO, Some("s2")),
+        ];
+        let page = page_transactions(transactions.clone(), Some(COIN_SOURCE_POMODORO), 1);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].reference_id.as_deref(), Some("s2"));
+        assert_eq!(page_transactions(transactions, None, 10).len(), 3);
+    }
+}
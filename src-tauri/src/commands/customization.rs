@@ -10,14 +10,6 @@ use crate::{
 const ALLOWED_THEMES: &[&str] = &["sunrise", "dusk", "mint", "mono"];
 const ALLOWED_SKINS: &[&str] = &["classic", "neon", "pixel", "plush"];
 const ALLOWED_SCENES: &[&str] = &["meadow", "forest", "space", "cozy_room"];
-const ALLOWED_ACCESSORIES: &[&str] = &[
-    "party_hat",
-    "bow_tie",
-    "sunglasses",
-    "scarf",
-    "apple",
-    "cookie",
-];
 const MAX_LOADOUTS: usize = 40;
 const MAX_LOADOUT_NAME_CHARS: usize = 64;
 
@@ -32,11 +24,7 @@ fn validate_variant(value: &str, allowed: &[&str], field: &str) -> Result<(), St
 fn sanitize_accessories(accessories: Vec<String>) -> Vec<String> {
     let mut sanitized = Vec::new();
     for accessory in accessories {
-        if ALLOWED_ACCESSORIES
-            .iter()
-            .any(|candidate| *candidate == accessory)
-            && !sanitized.contains(&accessory)
-        {
+        if crate::commands::shop::is_accessory_item(&accessory) && !sanitized.contains(&accessory) {
             sanitized.push(accessory);
         }
     }
@@ -60,7 +48,14 @@ fn sanitize_loadout(mut loadout: CustomizationLoadout) -> Result<CustomizationLo
     validate_variant(&loadout.pet_scene, ALLOWED_SCENES, "pet scene")?;
 
     loadout.name = name.to_string();
-    loadout.accessories = sanitize_accessories(loadout.accessories);
+    // Loadouts saved before slots keep a flat list; slot it the way a legacy pet is.
+    let legacy = sanitize_accessories(std::mem::take(&mut loadout.accessories));
+    for (slot, item_id) in crate::commands::shop::slots_from_list(&legacy) {
+        loadout.equipped.entry(slot).or_insert(item_id);
+    }
+    loadout.equipped.retain(|slot, item_id| {
+        crate::commands::shop::accessory_slot(item_id) == Some(slot.as_str())
+    });
     Ok(loadout)
 }
 
@@ -88,10 +83,47 @@ fn load_settings(app: &AppHandle) -> Result<Settings, String> {
 
 fn load_pet(app: &AppHandle) -> Result<PetState, String> {
     let store = app.store("store.json").map_err(|e| e.to_string())?;
-    Ok(store
+    let mut pet: PetState = store
         .get("pet")
         .and_then(|v| serde_json::from_value(v).ok())
-        .unwrap_or_default())
+        .unwrap_or_default();
+    crate::commands::shop::normalize_pet_items(&mut pet);
+    Ok(pet)
+}
+
+fn save_pet_and_emit(app: &AppHandle, pet: &PetState) -> Result<(), String> {
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    store.set("pet", json!(pet));
+    let _ = app.emit(EVENT_PET_STATE_CHANGED, pet);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn equip_accessory(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    item_id: String,
+) -> Result<PetState, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let mut pet = load_pet(&app)?;
+    crate::commands::shop::equip_item(&mut pet, &item_id)?;
+    save_pet_and_emit(&app, &pet)?;
+    Ok(pet)
+}
+
+#[tauri::command]
+pub fn unequip_accessory(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    item_id: String,
+) -> Result<PetState, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let mut pet = load_pet(&app)?;
+    if !crate::commands::shop::unequip_item(&mut pet, &item_id) {
+        return Err(format!("Accessory not equipped: {}", item_id));
+    }
+    save_pet_and_emit(&app, &pet)?;
+    Ok(pet)
 }
 
 #[tauri::command]
@@ -149,6 +181,15 @@ pub fn apply_customization_loadout(
         .ok_or_else(|| "Loadout not found".to_string())?;
     let loadout = sanitize_loadout(loadout)?;
 
+    let current_pet = load_pet(&app)?;
+    crate::leveling::ensure_cosmetic_unlocked(&app, "skin", &loadout.pet_skin, &current_pet.skin)?;
+    crate::leveling::ensure_cosmetic_unlocked(
+        &app,
+        "scene",
+        &loadout.pet_scene,
+        &current_pet.scene,
+    )?;
+
     let mut settings = load_settings(&app)?;
     settings.ui_theme = loadout.ui_theme.clone();
     settings.pet_skin = loadout.pet_skin.clone();
@@ -158,7 +199,8 @@ pub fn apply_customization_loadout(
     let mut pet = load_pet(&app)?;
     pet.skin = loadout.pet_skin.clone();
     pet.scene = loadout.pet_scene.clone();
-    pet.accessories = loadout.accessories.clone();
+    // Only accessories the pet owns go on, so a loadout never hands out unbought items.
+    crate::commands::shop::set_equipped(&mut pet, loadout.equipped.clone());
     store.set("pet", json!(pet));
 
     let _ = app.emit(EVENT_SETTINGS_CHANGED, settings);
@@ -171,6 +213,31 @@ mod tests {
     use super::{sanitize_accessories, sanitize_loadout, upsert_loadout};
     use crate::models::CustomizationLoadout;
 
+    #[test]
+    fn sanitize_loadout_slots_legacy_accessories() {
+        let loadout = CustomizationLoadout {
+            name: "Party".to_string(),
+            ui_theme: "sunrise".to_string(),
+            pet_skin: "classic".to_string(),
+            pet_scene: "meadow".to_string(),
+            accessories: vec!["party_hat".to_string(), "scarf".to_string()],
+            equipped: [("face".to_string(), "bow_tie".to_string())]
+                .into_iter()
+                .collect(),
+        };
+        let sanitized = sanitize_loadout(loadout).unwrap();
+        assert!(sanitized.accessories.is_empty());
+        assert_eq!(sanitized.equipped.len(), 2);
+        assert_eq!(
+            sanitized.equipped.get("head").map(String::as_str),
+            Some("party_hat")
+        );
+        assert_eq!(
+            sanitized.equipped.get("neck").map(String::as_str),
+            Some("scarf")
+        );
+    }
+
     #[test]
     fn sanitize_accessories_removes_unknown_and_dupes() {
         let sanitized = sanitize_accessories(vec![
@@ -189,6 +256,7 @@ mod tests {
             pet_skin: "classic".to_string(),
             pet_scene: "meadow".to_string(),
             accessories: vec![],
+            equipped: Default::default(),
         };
         let sanitized = sanitize_loadout(loadout).unwrap();
         assert_eq!(sanitized.name, "Cozy");
@@ -204,6 +272,7 @@ mod tests {
                 pet_skin: "classic".to_string(),
                 pet_scene: "meadow".to_string(),
                 accessories: vec![],
+                equipped: Default::default(),
             });
         }
 
@@ -215,6 +284,7 @@ mod tests {
                 pet_skin: "classic".to_string(),
                 pet_scene: "meadow".to_string(),
                 accessories: vec![],
+                equipped: Default::default(),
             },
         );
 
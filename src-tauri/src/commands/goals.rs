@@ -1,68 +1,518 @@
+use chrono::Datelike;
 use serde_json::json;
 use tauri::{AppHandle, Emitter};
 use tauri_plugin_store::StoreExt;
 
 use crate::events::EVENT_GOALS_CHANGED;
-use crate::models::DailyGoal;
+use crate::models::{
+    DailyGoal, DailySummary, GoalHistoryDay, GoalOutcome, GoalTemplate, GoalTemplatePatch,
+};
 
-fn today() -> String {
-    chrono::Utc::now().format("%Y-%m-%d").to_string()
+const GOAL_TEMPLATES_KEY: &str = "goal_templates";
+const GOALS_DATE_KEY: &str = "goals_date";
+const GOAL_HISTORY_KEY: &str = "goal_history";
+pub(crate) const MAX_GOAL_HISTORY_DAYS: usize = 90;
+const ADAPTIVE_WINDOW_DAYS: i64 = 14;
+const MIN_ADAPTIVE_SAMPLE_DAYS: usize = 3;
+/// Metrics recorded on `DailySummary`; only these can adapt.
+const ADAPTIVE_GOAL_METRICS: &[&str] = &["pomodoros", "focus_minutes", "tasks"];
+/// Hitting a goal this many days running raises its target by at least one.
+const ADAPTIVE_RAISE_STREAK_DAYS: u32 = 5;
+const MAX_GOAL_TEMPLATES: usize = 12;
+const MAX_COUNT_TARGET: u32 = 100;
+const MAX_FOCUS_MINUTES_TARGET: u32 = 720;
+const ALLOWED_GOAL_METRICS: &[&str] = &[
+    "pomodoros",
+    "focus_minutes",
+    "tasks",
+    "breaks",
+    "care_actions",
+    "quests",
+];
+
+fn template(id: &str, target: u32) -> GoalTemplate {
+    GoalTemplate {
+        id: id.to_string(),
+        metric: id.to_string(),
+        target,
+        enabled: true,
+        weekdays: vec![],
+    }
 }
 
-fn default_goals(date: &str) -> Vec<DailyGoal> {
+pub(crate) fn default_goal_templates() -> Vec<GoalTemplate> {
     vec![
-        DailyGoal {
-            id: "pomodoros".to_string(),
-            description: "Complete 4 pomodoros".to_string(),
-            target: 4,
-            progress: 0,
-            date: date.to_string(),
-        },
-        DailyGoal {
-            id: "breaks".to_string(),
-            description: "Take 3 breaks".to_string(),
-            target: 3,
-            progress: 0,
-            date: date.to_string(),
-        },
-        DailyGoal {
-            id: "tasks".to_string(),
-            description: "Complete 2 tasks".to_string(),
-            target: 2,
-            progress: 0,
-            date: date.to_string(),
-        },
-        DailyGoal {
-            id: "focus_minutes".to_string(),
-            description: "Focus for 60 minutes".to_string(),
-            target: 60,
+        template("pomodoros", 4),
+        template("breaks", 3),
+        template("tasks", 2),
+        template("focus_minutes", 60),
+    ]
+}
+
+fn max_goal_target(metric: &str) -> u32 {
+    if metric == "focus_minutes" {
+        MAX_FOCUS_MINUTES_TARGET
+    } else {
+        MAX_COUNT_TARGET
+    }
+}
+
+pub(crate) fn goal_description(metric: &str, target: u32) -> String {
+    let plural = if target == 1 { "" } else { "s" };
+    match metric {
+        "pomodoros" => format!("Complete {} pomodoro{}", target, plural),
+        "focus_minutes" => format!("Focus for {} minute{}", target, plural),
+        "tasks" => format!("Complete {} task{}", target, plural),
+        "breaks" => format!("Take {} break{}", target, plural),
+        "care_actions" => format!("Do {} care action{}", target, plural),
+        _ => format!("Finish {} quest{}", target, plural),
+    }
+}
+
+fn normalize_goal_metric(metric: &str) -> Result<String, String> {
+    let normalized = metric.trim().to_ascii_lowercase();
+    if ALLOWED_GOAL_METRICS.contains(&normalized.as_str()) {
+        Ok(normalized)
+    } else {
+        Err(format!("Invalid goal metric: {}", metric))
+    }
+}
+
+fn normalize_goal_weekdays(weekdays: Vec<u8>) -> Result<Vec<u8>, String> {
+    if let Some(day) = weekdays.iter().find(|day| **day > 6) {
+        return Err(format!("Invalid weekday: {}", day));
+    }
+    let mut weekdays = weekdays;
+    weekdays.sort_unstable();
+    weekdays.dedup();
+    Ok(weekdays)
+}
+
+fn validate_goal_template(template: &mut GoalTemplate) -> Result<(), String> {
+    template.metric = normalize_goal_metric(&template.metric)?;
+    let max_target = max_goal_target(&template.metric);
+    if template.target == 0 || template.target > max_target {
+        return Err(format!("Goal target must be between 1 and {}", max_target));
+    }
+    template.weekdays = normalize_goal_weekdays(std::mem::take(&mut template.weekdays))?;
+    Ok(())
+}
+
+/// Drops invalid or duplicate templates and clamps targets, for stored or imported data.
+pub(crate) fn sanitize_goal_templates(templates: &mut Vec<GoalTemplate>) {
+    let mut seen: Vec<String> = Vec::new();
+    templates.retain_mut(|template| {
+        if template.id.is_empty() || seen.contains(&template.id) {
+            return false;
+        }
+        let Ok(metric) = normalize_goal_metric(&template.metric) else {
+            return false;
+        };
+        template.target = template.target.clamp(1, max_goal_target(&metric));
+        template.metric = metric;
+        template.weekdays.retain(|day| *day <= 6);
+        template.weekdays.sort_unstable();
+        template.weekdays.dedup();
+        seen.push(template.id.clone());
+        true
+    });
+    templates.truncate(MAX_GOAL_TEMPLATES);
+}
+
+fn template_applies_on(template: &GoalTemplate, date: &str) -> bool {
+    if !template.enabled {
+        return false;
+    }
+    if template.weekdays.is_empty() {
+        return true;
+    }
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|day| {
+            let weekday = day.weekday().num_days_from_monday() as u8;
+            template.weekdays.contains(&weekday)
+        })
+        .unwrap_or(true)
+}
+
+fn goals_for_date(templates: &[GoalTemplate], date: &str) -> Vec<DailyGoal> {
+    templates
+        .iter()
+        .filter(|template| template_applies_on(template, date))
+        .map(|template| DailyGoal {
+            id: template.id.clone(),
+            description: goal_description(&template.metric, template.target),
+            target: template.target,
             progress: 0,
             date: date.to_string(),
-        },
-    ]
+            metric: template.metric.clone(),
+            target_reason: None,
+        })
+        .collect()
+}
+
+fn summary_value(summary: &DailySummary, metric: &str) -> Option<u32> {
+    match metric {
+        "pomodoros" => Some(summary.sessions_completed),
+        "focus_minutes" => Some(summary.focus_minutes),
+        "tasks" => Some(summary.tasks_completed),
+        _ => None,
+    }
+}
+
+fn median(mut values: Vec<u32>) -> u32 {
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2
+    } else {
+        values[mid]
+    }
+}
+
+/// Consecutive days before `today` on which `goal_id` was completed; a day missing from
+/// the history ends the streak.
+fn completion_streak(history: &[GoalHistoryDay], goal_id: &str, today: chrono::NaiveDate) -> u32 {
+    let mut streak = 0;
+    let mut day = today;
+    while (streak as usize) < history.len() {
+        let Some(previous) = day.pred_opt() else {
+            break;
+        };
+        day = previous;
+        let date = day.format("%Y-%m-%d").to_string();
+        let completed = history.iter().any(|entry| {
+            entry.date == date
+                && entry
+                    .goals
+                    .iter()
+                    .any(|goal| goal.id == goal_id && goal.completed)
+        });
+        if !completed {
+            break;
+        }
+        streak += 1;
+    }
+    streak
+}
+
+/// Sets the target to the recent median plus `stretch_percent`, within half and double it.
+fn adapt_goal_target(
+    goal: &mut DailyGoal,
+    summaries: &[DailySummary],
+    history: &[GoalHistoryDay],
+    stretch_percent: u32,
+) {
+    let Ok(today) = chrono::NaiveDate::parse_from_str(&goal.date, "%Y-%m-%d") else {
+        return;
+    };
+    let window_start = (today - chrono::Duration::days(ADAPTIVE_WINDOW_DAYS))
+        .format("%Y-%m-%d")
+        .to_string();
+    if !ADAPTIVE_GOAL_METRICS.contains(&goal.metric.as_str()) {
+        return;
+    }
+    let mut samples: Vec<u32> = summaries
+        .iter()
+        .filter(|summary| summary.date >= window_start && summary.date < goal.date)
+        .filter_map(|summary| summary_value(summary, &goal.metric))
+        .collect();
+    if samples.len() < MIN_ADAPTIVE_SAMPLE_DAYS {
+        goal.target_reason =
+            Some("Not enough recent history yet, using your set target".to_string());
+        return;
+    }
+    // Days without a summary were idle, so they count as zero in the median.
+    samples.resize(ADAPTIVE_WINDOW_DAYS as usize, 0);
+
+    let template_target = goal.target;
+    let floor = (template_target / 2).max(1);
+    let ceiling = template_target
+        .saturating_mul(2)
+        .min(max_goal_target(&goal.metric))
+        .max(floor);
+    let previous_target = history
+        .first()
+        .and_then(|day| day.goals.iter().find(|g| g.id == goal.id))
+        .map(|g| g.target)
+        .unwrap_or(template_target);
+    let streak = completion_streak(history, &goal.id, today);
+
+    let recent_median = median(samples);
+    let mut target = recent_median + (recent_median * stretch_percent).div_ceil(100);
+    let streak_raise = streak >= ADAPTIVE_RAISE_STREAK_DAYS && target <= previous_target;
+    if streak_raise {
+        target = previous_target + 1;
+    }
+    let clamped = target.clamp(floor, ceiling);
+
+    let reason = if clamped != target {
+        let bound = if clamped == floor {
+            "minimum"
+        } else {
+            "maximum"
+        };
+        format!(
+            "Target held at the {} of {} (14-day median: {})",
+            bound, clamped, recent_median
+        )
+    } else if clamped > previous_target && streak >= ADAPTIVE_RAISE_STREAK_DAYS {
+        format!("Target raised because you hit it {} days in a row", streak)
+    } else if clamped > previous_target {
+        format!("Target raised from your 14-day median of {}", recent_median)
+    } else if clamped < previous_target {
+        format!("Target lowered to your 14-day median of {}", recent_median)
+    } else {
+        format!("Target kept at your 14-day median of {}", recent_median)
+    };
+
+    goal.target = clamped;
+    goal.description = goal_description(&goal.metric, clamped);
+    goal.target_reason = Some(reason);
+}
+
+fn apply_adaptive_targets(app: &AppHandle, goals: &mut [DailyGoal]) -> Result<(), String> {
+    let settings = crate::commands::settings::get_settings(app.clone())?;
+    if !settings.adaptive_goals_enabled {
+        return Ok(());
+    }
+    let summaries = crate::progression::get_daily_summaries(app, ADAPTIVE_WINDOW_DAYS as u32 + 1)?;
+    let history = load_goal_history(app)?;
+    for goal in goals.iter_mut() {
+        adapt_goal_target(
+            goal,
+            &summaries,
+            &history,
+            settings.adaptive_goal_stretch_percent,
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+fn default_goals(date: &str) -> Vec<DailyGoal> {
+    goals_for_date(&default_goal_templates(), date)
+}
+
+pub(crate) fn load_goal_templates(app: &AppHandle) -> Result<Vec<GoalTemplate>, String> {
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    let mut templates = store
+        .get(GOAL_TEMPLATES_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_else(default_goal_templates);
+    sanitize_goal_templates(&mut templates);
+    Ok(templates)
+}
+
+fn save_goal_templates(app: &AppHandle, templates: &[GoalTemplate]) -> Result<(), String> {
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    store.set(GOAL_TEMPLATES_KEY, json!(templates));
+    Ok(())
+}
+
+fn fill_legacy_metrics(goals: &mut [DailyGoal]) {
+    for goal in goals.iter_mut() {
+        if goal.metric.is_empty() {
+            goal.metric = goal.id.clone();
+        }
+    }
+}
+
+/// Stores the final outcome of `goals` under their date, newest day first.
+fn record_goal_history(history: &mut Vec<GoalHistoryDay>, goals: &[DailyGoal]) {
+    let Some(date) = goals.first().map(|goal| goal.date.clone()) else {
+        return;
+    };
+    let outcomes = goals
+        .iter()
+        .filter(|goal| goal.date == date)
+        .map(|goal| GoalOutcome {
+            id: goal.id.clone(),
+            metric: goal.metric.clone(),
+            target: goal.target,
+            progress: goal.progress,
+            completed: goal.progress >= goal.target,
+        })
+        .collect();
+    history.retain(|day| day.date != date);
+    history.push(GoalHistoryDay {
+        date,
+        goals: outcomes,
+    });
+    sanitize_goal_history(history);
+}
+
+pub(crate) fn sanitize_goal_history(history: &mut Vec<GoalHistoryDay>) {
+    history.sort_by(|a, b| b.date.cmp(&a.date));
+    history.dedup_by(|a, b| a.date == b.date);
+    history.truncate(MAX_GOAL_HISTORY_DAYS);
+}
+
+fn load_goal_history(app: &AppHandle) -> Result<Vec<GoalHistoryDay>, String> {
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    Ok(store
+        .get(GOAL_HISTORY_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+fn archive_goals(app: &AppHandle, goals: &[DailyGoal]) -> Result<(), String> {
+    if goals.is_empty() {
+        return Ok(());
+    }
+    let mut history = load_goal_history(app)?;
+    record_goal_history(&mut history, goals);
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    store.set(GOAL_HISTORY_KEY, json!(history));
+    Ok(())
 }
 
 fn load_goals(app: &AppHandle) -> Result<Vec<DailyGoal>, String> {
     let store = app.store("store.json").map_err(|e| e.to_string())?;
-    let goals: Vec<DailyGoal> = store
+    let mut goals: Vec<DailyGoal> = store
         .get("goals")
         .and_then(|v| serde_json::from_value(v).ok())
         .unwrap_or_default();
+    fill_legacy_metrics(&mut goals);
 
-    let date = today();
-    let has_current_date = !goals.is_empty() && goals.iter().all(|goal| goal.date == date);
+    let date = crate::clock::today(app);
+    let generated_on: Option<String> = store
+        .get(GOALS_DATE_KEY)
+        .and_then(|v| serde_json::from_value(v).ok());
+    let has_current_date = generated_on.as_deref() == Some(date.as_str())
+        || (!goals.is_empty() && goals.iter().all(|goal| goal.date == date));
     if !has_current_date {
-        let new_goals = default_goals(&date);
+        archive_goals(app, &goals)?;
+        let mut new_goals = goals_for_date(&load_goal_templates(app)?, &date);
+        apply_adaptive_targets(app, &mut new_goals)?;
         store.set("goals", json!(new_goals));
-        Ok(store
-            .get("goals")
-            .and_then(|v| serde_json::from_value(v).ok())
-            .unwrap_or_default())
+        store.set(GOALS_DATE_KEY, json!(date));
+        Ok(new_goals)
     } else {
         Ok(goals)
     }
 }
 
+/// Rebuilds today's goals after a template change, keeping progress already made.
+fn rebuild_today_goals(app: &AppHandle) -> Result<Vec<DailyGoal>, String> {
+    let current = load_goals(app)?;
+    let date = crate::clock::today(app);
+    let mut goals = goals_for_date(&load_goal_templates(app)?, &date);
+    apply_adaptive_targets(app, &mut goals)?;
+    for goal in goals.iter_mut() {
+        if let Some(existing) = current.iter().find(|g| g.id == goal.id && g.date == date) {
+            goal.progress = existing.progress.min(goal.target);
+        }
+    }
+
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    store.set("goals", json!(goals));
+    store.set(GOALS_DATE_KEY, json!(date));
+    let _ = app.emit(EVENT_GOALS_CHANGED, &goals);
+    Ok(goals)
+}
+
+#[tauri::command]
+pub fn get_goal_templates(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+) -> Result<Vec<GoalTemplate>, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    load_goal_templates(&app)
+}
+
+#[tauri::command]
+pub fn create_goal_template(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    metric: String,
+    target: u32,
+    weekdays: Option<Vec<u8>>,
+) -> Result<GoalTemplate, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let mut templates = load_goal_templates(&app)?;
+    if templates.len() >= MAX_GOAL_TEMPLATES {
+        return Err(format!(
+            "Cannot have more than {} goals",
+            MAX_GOAL_TEMPLATES
+        ));
+    }
+    let mut template = GoalTemplate {
+        id: uuid::Uuid::new_v4().to_string(),
+        metric,
+        target,
+        enabled: true,
+        weekdays: weekdays.unwrap_or_default(),
+    };
+    validate_goal_template(&mut template)?;
+    templates.push(template.clone());
+    save_goal_templates(&app, &templates)?;
+    rebuild_today_goals(&app)?;
+    Ok(template)
+}
+
+#[tauri::command]
+pub fn update_goal_template(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    template_id: String,
+    patch: GoalTemplatePatch,
+) -> Result<GoalTemplate, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let mut templates = load_goal_templates(&app)?;
+    let template = templates
+        .iter_mut()
+        .find(|t| t.id == template_id)
+        .ok_or_else(|| "Goal template not found".to_string())?;
+    let mut updated = template.clone();
+    patch.apply_to(&mut updated);
+    validate_goal_template(&mut updated)?;
+    *template = updated.clone();
+    save_goal_templates(&app, &templates)?;
+    rebuild_today_goals(&app)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+pub fn delete_goal_template(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    template_id: String,
+) -> Result<Vec<GoalTemplate>, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let mut templates = load_goal_templates(&app)?;
+    let before = templates.len();
+    templates.retain(|t| t.id != template_id);
+    if templates.len() == before {
+        return Err("Goal template not found".to_string());
+    }
+    save_goal_templates(&app, &templates)?;
+    rebuild_today_goals(&app)?;
+    Ok(templates)
+}
+
+/// Returns archived goal outcomes for up to `days` past days, newest first.
+#[tauri::command]
+pub fn get_goal_history(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    days: Option<u32>,
+) -> Result<Vec<GoalHistoryDay>, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    // Roll over first so yesterday's goals are included once the day changes.
+    load_goals(&app)?;
+    let mut history = load_goal_history(&app)?;
+    sanitize_goal_history(&mut history);
+    history.truncate(days.unwrap_or(14).clamp(1, MAX_GOAL_HISTORY_DAYS as u32) as usize);
+    Ok(history)
+}
+
+/// Re-emits today's goals so listeners refresh after weekly or monthly goals change.
+pub(crate) fn emit_goals_changed(app: &AppHandle) -> Result<(), String> {
+    let goals = load_goals(app)?;
+    let _ = app.emit(EVENT_GOALS_CHANGED, &goals);
+    Ok(())
+}
+
 #[tauri::command]
 pub fn get_daily_goals(
     app: AppHandle,
@@ -72,25 +522,54 @@ pub fn get_daily_goals(
     load_goals(&app)
 }
 
-pub fn increment_goal_progress(app: &AppHandle, goal_id: &str) -> Result<(), String> {
-    save_goal_progress_delta(app, goal_id, 1)?;
+pub fn increment_goal_progress(app: &AppHandle, metric: &str) -> Result<(), String> {
+    save_goal_progress_delta(app, metric, 1)?;
     Ok(())
 }
 
-pub fn add_goal_progress(app: &AppHandle, goal_id: &str, delta: u32) -> Result<(), String> {
-    save_goal_progress_delta(app, goal_id, delta)
+pub fn add_goal_progress(app: &AppHandle, metric: &str, delta: u32) -> Result<(), String> {
+    save_goal_progress_delta(app, metric, delta)
 }
 
-fn save_goal_progress_delta(app: &AppHandle, goal_id: &str, delta: u32) -> Result<(), String> {
+/// Takes back progress credited by a reward that was later clawed back.
+/// Progress credited on an earlier day has already rolled over and is left alone.
+pub fn remove_goal_progress(
+    app: &AppHandle,
+    metric: &str,
+    credited_on: &str,
+    delta: u32,
+) -> Result<(), String> {
     let mut goals = load_goals(app)?;
-    let mut newly_completed = false;
+    let mut changed = false;
+    for goal in goals
+        .iter_mut()
+        .filter(|g| g.metric == metric && g.date == credited_on)
+    {
+        goal.progress = goal.progress.saturating_sub(delta);
+        changed = true;
+    }
+    if !changed {
+        return Ok(());
+    }
 
-    if let Some(goal) = goals.iter_mut().find(|g| g.id == goal_id) {
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    store.set("goals", json!(goals));
+    let _ = app.emit(EVENT_GOALS_CHANGED, &goals);
+    Ok(())
+}
+
+fn save_goal_progress_delta(app: &AppHandle, metric: &str, delta: u32) -> Result<(), String> {
+    let mut goals = load_goals(app)?;
+    let mut completed_goals = Vec::new();
+
+    for goal in goals.iter_mut().filter(|g| g.metric == metric) {
         let was_complete = goal.progress >= goal.target;
         if !was_complete {
             goal.progress = goal.progress.saturating_add(delta).min(goal.target);
             let now_complete = goal.progress >= goal.target;
-            newly_completed = now_complete && !was_complete;
+            if now_complete {
+                completed_goals.push(goal.clone());
+            }
         }
     }
 
@@ -98,13 +577,110 @@ fn save_goal_progress_delta(app: &AppHandle, goal_id: &str, delta: u32) -> Resul
     store.set("goals", json!(goals));
     let _ = app.emit(EVENT_GOALS_CHANGED, &goals);
 
-    if newly_completed {
-        let _ = crate::progression::record_goal_completion(app);
+    for goal in &completed_goals {
+        let _ = reward_goal_completion(app, goal);
+    }
+    if !completed_goals.is_empty() {
+        let _ = reward_perfect_day(app, &goals);
+    }
+
+    Ok(())
+}
+
+/// Rewards are keyed by metric rather than template id, so recreating a template
+/// doesn't pay the same day's goal again.
+fn goal_reward_source_id(goal: &DailyGoal) -> String {
+    let metric = if goal.metric.is_empty() {
+        &goal.id
+    } else {
+        &goal.metric
+    };
+    format!("{}:{}", goal.date, metric)
+}
+
+fn pay_goal_coins(
+    app: &AppHandle,
+    amount: u32,
+    source: &str,
+    reference_id: &str,
+) -> Result<(), String> {
+    if amount > 0 {
+        crate::commands::coins::credit_coins(app, amount, source, Some(reference_id))?;
+        crate::progression::record_coins_earned(app, amount)?;
+    }
+    Ok(())
+}
+
+/// Pays the goal XP and coins once per goal and day, even if progress is edited back and forth.
+fn reward_goal_completion(app: &AppHandle, goal: &DailyGoal) -> Result<(), String> {
+    let source_id = goal_reward_source_id(goal);
+    if crate::rewards::claim(
+        app,
+        crate::rewards::SOURCE_GOAL,
+        &source_id,
+        crate::rewards::REWARD_XP,
+        crate::progression::XP_PER_GOAL_COMPLETION,
+    )? {
+        crate::progression::record_goal_completion(
+            app,
+            crate::progression::XP_PER_GOAL_COMPLETION,
+        )?;
     }
 
+    let coins = crate::commands::settings::get_settings(app.clone())?.goal_reward_coins;
+    if crate::rewards::claim(
+        app,
+        crate::rewards::SOURCE_GOAL,
+        &source_id,
+        crate::rewards::REWARD_COINS,
+        coins,
+    )? {
+        pay_goal_coins(
+            app,
+            coins,
+            crate::commands::coins::COIN_SOURCE_GOAL,
+            &source_id,
+        )?;
+    }
     Ok(())
 }
 
+fn is_perfect_day(goals: &[DailyGoal], date: &str) -> bool {
+    let todays: Vec<&DailyGoal> = goals.iter().filter(|g| g.date == date).collect();
+    !todays.is_empty() && todays.iter().all(|g| g.progress >= g.target)
+}
+
+/// Pays the all-goals-complete bonus once per day.
+fn reward_perfect_day(app: &AppHandle, goals: &[DailyGoal]) -> Result<(), String> {
+    let date = crate::clock::today(app);
+    if !is_perfect_day(goals, &date) {
+        return Ok(());
+    }
+    let settings = crate::commands::settings::get_settings(app.clone())?;
+    if !crate::rewards::claim(
+        app,
+        crate::rewards::SOURCE_PERFECT_DAY,
+        &date,
+        crate::rewards::REWARD_COINS,
+        settings.perfect_day_bonus_coins,
+    )? {
+        return Ok(());
+    }
+    pay_goal_coins(
+        app,
+        settings.perfect_day_bonus_coins,
+        crate::commands::coins::COIN_SOURCE_PERFECT_DAY,
+        &date,
+    )?;
+    crate::commands::pet::celebrate_perfect_day(
+        app,
+        settings.perfect_day_affection_boost,
+        settings.perfect_day_bonus_coins,
+    )?;
+    Ok(())
+}
+
+/// Sets a goal's progress by hand. Only tracked activity pays goal rewards, so this never does.
 #[tauri::command]
 pub fn update_goal_progress(
     app: AppHandle,
@@ -114,20 +690,14 @@ pub fn update_goal_progress(
 ) -> Result<Vec<DailyGoal>, String> {
     let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
     let mut goals = load_goals(&app)?;
-    let newly_completed = if let Some(goal) = goals.iter_mut().find(|g| g.id == goal_id) {
-        let was_complete = goal.progress >= goal.target;
-        goal.progress = progress.min(goal.target);
-        let now_complete = goal.progress >= goal.target;
-        now_complete && !was_complete
-    } else {
-        return Err("Goal not found".to_string());
-    };
+    let goal = goals
+        .iter_mut()
+        .find(|g| g.id == goal_id)
+        .ok_or_else(|| "Goal not found".to_string())?;
+    goal.progress = progress.min(goal.target);
     let store = app.store("store.json").map_err(|e| e.to_string())?;
     store.set("goals", json!(goals));
     let _ = app.emit(EVENT_GOALS_CHANGED, &goals);
-    if newly_completed {
-        let _ = crate::progression::record_goal_completion(&app);
-    }
     Ok(goals)
 }
 
@@ -214,11 +784,262 @@ mod tests {
         assert_eq!(focus.target, 60);
     }
 
+    fn custom_template(metric: &str, target: u32, weekdays: &[u8]) -> GoalTemplate {
+        GoalTemplate {
+            id: format!("{metric}-custom"),
+            metric: metric.to_string(),
+            target,
+            enabled: true,
+            weekdays: weekdays.to_vec(),
+        }
+    }
+
+    #[test]
+    fn goals_for_date_follows_weekday_schedule_and_enabled_flag() {
+        let mut disabled = custom_template("quests", 1, &[]);
+        disabled.enabled = false;
+        let templates = vec![
+            custom_template("care_actions", 3, &[5, 6]),
+            custom_template("tasks", 5, &[0]),
+            disabled,
+        ];
+        // 2026-01-03 is a Saturday, 2026-01-05 a Monday.
+        let saturday = goals_for_date(&templates, "2026-01-03");
+        assert_eq!(saturday.len(), 1);
+        assert_eq!(saturday[0].metric, "care_actions");
+        assert_eq!(saturday[0].description, "Do 3 care actions");
+        let monday = goals_for_date(&templates, "2026-01-05");
+        assert_eq!(monday.len(), 1);
+        assert_eq!(monday[0].id, "tasks-custom");
+    }
+
+    #[test]
+    fn validate_goal_template_checks_metric_target_and_weekdays() {
+        let mut valid = custom_template(" Focus_Minutes ", 90, &[4, 0, 4]);
+        validate_goal_template(&mut valid).unwrap();
+        assert_eq!(valid.metric, "focus_minutes");
+        assert_eq!(valid.weekdays, vec![0, 4]);
+
+        assert!(validate_goal_template(&mut custom_template("steps", 10, &[])).is_err());
+        assert!(validate_goal_template(&mut custom_template("tasks", 0, &[])).is_err());
+        assert!(
+            validate_goal_template(&mut custom_template("tasks", MAX_COUNT_TARGET + 1, &[]))
+                .is_err()
+        );
+        assert!(validate_goal_template(&mut custom_template("tasks", 2, &[7])).is_err());
+    }
+
+    #[test]
+    fn sanitize_goal_templates_drops_invalid_and_duplicates() {
+        let mut templates = vec![
+            custom_template("tasks", 500, &[9, 1]),
+            custom_template("tasks", 2, &[]),
+            custom_template("steps", 2, &[]),
+        ];
+        sanitize_goal_templates(&mut templates);
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].target, MAX_COUNT_TARGET);
+        assert_eq!(templates[0].weekdays, vec![1]);
+    }
+
+    #[test]
+    fn fill_legacy_metrics_uses_goal_id() {
+        let mut goals = default_goals("2025-01-01");
+        goals[0].metric.clear();
+        fill_legacy_metrics(&mut goals);
+        assert_eq!(goals[0].metric, "pomodoros");
+    }
+
+    #[test]
+    fn record_goal_history_keeps_final_outcomes_newest_first() {
+        let mut history = Vec::new();
+        let mut day_one = default_goals("2026-03-01");
+        day_one[0].progress = 4;
+        day_one[1].progress = 1;
+        record_goal_history(&mut history, &day_one);
+        record_goal_history(&mut history, &default_goals("2026-03-02"));
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].date, "2026-03-02");
+        let outcomes = &history[1].goals;
+        assert_eq!(outcomes.len(), 4);
+        assert!(outcomes[0].completed);
+        assert_eq!(outcomes[1].progress, 1);
+        assert!(!outcomes[1].completed);
+    }
+
+    #[test]
+    fn record_goal_history_replaces_same_day_and_is_bounded() {
+        let mut history = Vec::new();
+        for day in 1..=(MAX_GOAL_HISTORY_DAYS as u32 + 5) {
+            let date = chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()
+                + chrono::Duration::days(day as i64);
+            record_goal_history(
+                &mut history,
+                &default_goals(&date.format("%Y-%m-%d").to_string()),
+            );
+        }
+        assert_eq!(history.len(), MAX_GOAL_HISTORY_DAYS);
+
+        let newest = history[0].date.clone();
+        let mut finished = default_goals(&newest);
+        finished[2].progress = 2;
+        record_goal_history(&mut history, &finished);
+        assert_eq!(history.len(), MAX_GOAL_HISTORY_DAYS);
+        assert!(history[0].goals[2].completed);
+    }
+
+    fn summary(date: &str, sessions: u32) -> DailySummary {
+        DailySummary {
+            date: date.to_string(),
+            sessions_completed: sessions,
+            ..Default::default()
+        }
+    }
+
+    /// Summaries for every day of the window before 2026-03-15, newest first.
+    fn full_window(sessions: u32) -> Vec<DailySummary> {
+        (1..15)
+            .rev()
+            .map(|day| summary(&format!("2026-03-{day:02}"), sessions))
+            .collect()
+    }
+
+    fn completed_day(date: &str, target: u32) -> GoalHistoryDay {
+        GoalHistoryDay {
+            date: date.to_string(),
+            goals: vec![GoalOutcome {
+                id: "pomodoros".to_string(),
+                metric: "pomodoros".to_string(),
+                target,
+                progress: target,
+                completed: true,
+            }],
+        }
+    }
+
+    fn pomodoro_goal() -> DailyGoal {
+        default_goals("2026-03-15").remove(0)
+    }
+
+    #[test]
+    fn adaptive_target_uses_window_median_plus_stretch() {
+        let mut summaries = full_window(5);
+        summaries[0].sessions_completed = 6;
+        summaries.push(summary("2026-03-15", 40));
+        summaries.push(summary("2026-02-01", 40));
+        let mut goal = pomodoro_goal();
+        adapt_goal_target(&mut goal, &summaries, &[], 10);
+        assert_eq!(goal.target, 6);
+        assert_eq!(goal.description, "Complete 6 pomodoros");
+        assert_eq!(
+            goal.target_reason.as_deref(),
+            Some("Target raised from your 14-day median of 5")
+        );
+    }
+
+    #[test]
+    fn adaptive_target_is_clamped_to_template_bounds() {
+        let summaries = vec![
+            summary("2026-03-14", 0),
+            summary("2026-03-13", 0),
+            summary("2026-03-12", 1),
+        ];
+        let mut goal = pomodoro_goal();
+        adapt_goal_target(&mut goal, &summaries, &[], 10);
+        assert_eq!(goal.target, 2);
+        assert!(goal.target_reason.unwrap().contains("minimum"));
+
+        let busy = full_window(20);
+        let mut goal = pomodoro_goal();
+        adapt_goal_target(&mut goal, &busy, &[], 10);
+        assert_eq!(goal.target, 8);
+    }
+
+    #[test]
+    fn adaptive_target_rises_after_completion_streak() {
+        let summaries = full_window(4);
+        let history: Vec<GoalHistoryDay> = (10..15)
+            .rev()
+            .map(|day| completed_day(&format!("2026-03-{day}"), 4))
+            .collect();
+        let mut goal = pomodoro_goal();
+        adapt_goal_target(&mut goal, &summaries, &history, 0);
+        assert_eq!(goal.target, 5);
+        assert_eq!(
+            goal.target_reason.as_deref(),
+            Some("Target raised because you hit it 5 days in a row")
+        );
+    }
+
+    #[test]
+    fn completion_streak_stops_at_the_first_missing_day() {
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 3, 15).unwrap();
+        let history = vec![
+            completed_day("2026-03-14", 4),
+            completed_day("2026-03-13", 4),
+            completed_day("2026-03-11", 4),
+            completed_day("2026-03-10", 4),
+        ];
+        assert_eq!(completion_streak(&history, "pomodoros", today), 2);
+        assert_eq!(completion_streak(&history[2..], "pomodoros", today), 0);
+    }
+
+    #[test]
+    fn adaptive_target_counts_idle_days_as_zero() {
+        let summaries = vec![
+            summary("2026-03-14", 8),
+            summary("2026-03-13", 8),
+            summary("2026-03-12", 8),
+        ];
+        let mut goal = pomodoro_goal();
+        adapt_goal_target(&mut goal, &summaries, &[], 10);
+        assert_eq!(goal.target, 2);
+        assert!(goal.target_reason.unwrap().contains("minimum"));
+    }
+
+    #[test]
+    fn adaptive_target_needs_enough_samples_and_tracked_metric() {
+        let summaries = vec![summary("2026-03-14", 9)];
+        let mut goal = pomodoro_goal();
+        adapt_goal_target(&mut goal, &summaries, &[], 10);
+        assert_eq!(goal.target, 4);
+        assert!(goal.target_reason.is_some());
+
+        let mut breaks = default_goals("2026-03-15").remove(1);
+        adapt_goal_target(&mut breaks, &summaries, &[], 10);
+        assert_eq!(breaks.target, 3);
+        assert_eq!(breaks.target_reason, None);
+    }
+
+    #[test]
+    fn perfect_day_requires_every_goal_for_today() {
+        let mut goals = default_goals("2026-03-15");
+        assert!(!is_perfect_day(&goals, "2026-03-15"));
+        for goal in goals.iter_mut() {
+            goal.progress = goal.target;
+        }
+        assert!(is_perfect_day(&goals, "2026-03-15"));
+        assert!(!is_perfect_day(&goals, "2026-03-16"));
+        assert!(!is_perfect_day(&[], "2026-03-15"));
+    }
+
+    #[test]
+    fn goal_description_handles_singular() {
+        assert_eq!(goal_description("quests", 1), "Finish 1 quest");
+        assert_eq!(goal_description("pomodoros", 4), "Complete 4 pomodoros");
+    }
+
+    #[test]
+    fn goal_reward_source_id_is_scoped_to_date() {
+        let goals = default_goals("2025-01-01");
+        assert_eq!(goal_reward_source_id(&goals[0]), "2025-01-01:pomodoros");
+    }
+
     #[test]
-    fn today_returns_valid_date_format() {
-        let date = today();
-        assert_eq!(date.len(), 10);
-        assert!(date.chars().nth(4).unwrap() == '-');
-        assert!(date.chars().nth(7).unwrap() == '-');
+    fn goal_reward_source_id_ignores_the_template_id() {
+        let mut goal = default_goals("2025-01-01").remove(2);
+        goal.id = "6f1c2d9e-template".to_string();
+        assert_eq!(goal_reward_source_id(&goal), "2025-01-01:tasks");
     }
 }
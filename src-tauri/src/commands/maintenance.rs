@@ -7,10 +7,10 @@ use crate::{
         EVENT_ANALYTICS_CHANGED, EVENT_COINS_CHANGED, EVENT_GOALS_CHANGED, EVENT_PET_STATE_CHANGED,
         EVENT_PROFILE_CHANGED, EVENT_SETTINGS_CHANGED, EVENT_TIMER_RUNTIME_CHANGED,
     },
-    models::{AppDiagnostics, AppSnapshot, CURRENT_SCHEMA_VERSION},
+    models::{AppDiagnostics, AppSnapshot, ProgressRebuildReport, CURRENT_SCHEMA_VERSION},
 };
 
-const MAX_TASKS: usize = 2_000;
+const MAX_TASKS: usize = crate::commands::tasks::MAX_STORED_TASKS;
 const MAX_SESSIONS: usize = 10_000;
 const MAX_SUMMARIES: usize = 365;
 const MAX_LOADOUTS: usize = 200;
@@ -27,10 +27,21 @@ fn sanitize_snapshot(mut snapshot: AppSnapshot) -> AppSnapshot {
     snapshot.schema_version = CURRENT_SCHEMA_VERSION;
 
     crate::commands::settings::sanitize_settings(&mut snapshot.settings);
+    crate::commands::shop::normalize_pet_items(&mut snapshot.pet);
 
     snapshot.tasks.retain(|task| !task.title.trim().is_empty());
     for task in &mut snapshot.tasks {
-        task.title = task.title.trim().chars().take(140).collect();
+        crate::commands::tasks::sanitize_task(task);
+    }
+    crate::commands::tasks::sanitize_task_lists(&mut snapshot.task_lists);
+    crate::commands::tasks::reassign_orphaned_tasks(&mut snapshot.tasks, &snapshot.task_lists);
+    if let Some(templates) = snapshot.goal_templates.as_mut() {
+        crate::commands::goals::sanitize_goal_templates(templates);
+    }
+    crate::commands::goals::sanitize_goal_history(&mut snapshot.goal_history);
+    crate::commands::analytics::sanitize_summary_rollups(&mut snapshot.summary_rollups);
+    if let Some(templates) = snapshot.period_goal_templates.as_mut() {
+        crate::commands::period_goals::sanitize_period_goal_templates(templates);
     }
 
     snapshot.timer_runtime.total_seconds =
@@ -46,6 +57,7 @@ fn sanitize_snapshot(mut snapshot: AppSnapshot) -> AppSnapshot {
     cap_len(&mut snapshot.customization_loadouts, MAX_LOADOUTS);
     cap_len(&mut snapshot.pet_events, MAX_PET_EVENTS);
     cap_len(&mut snapshot.focus_guardrail_events, MAX_GUARDRAIL_EVENTS);
+    crate::rewards::dedupe_ledger(&mut snapshot.reward_ledger);
 
     snapshot
 }
@@ -74,6 +86,10 @@ fn load_snapshot(app: &AppHandle) -> Result<AppSnapshot, String> {
             .get("tasks")
             .and_then(|v| serde_json::from_value(v).ok())
             .unwrap_or_default(),
+        task_lists: store
+            .get("task_lists")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default(),
         goals: store
             .get("goals")
             .and_then(|v| serde_json::from_value(v).ok())
@@ -114,6 +130,27 @@ fn load_snapshot(app: &AppHandle) -> Result<AppSnapshot, String> {
             .get("focus_guardrail_events")
             .and_then(|v| serde_json::from_value(v).ok())
             .unwrap_or_default(),
+        reward_ledger: store
+            .get("reward_ledger")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default(),
+        goal_templates: store
+            .get("goal_templates")
+            .and_then(|v| serde_json::from_value(v).ok()),
+        goal_history: store
+            .get("goal_history")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default(),
+        period_goal_templates: store
+            .get("period_goal_templates")
+            .and_then(|v| serde_json::from_value(v).ok()),
+        coin_transactions: store
+            .get("coin_transactions")
+            .and_then(|v| serde_json::from_value(v).ok()),
+        summary_rollups: store
+            .get("summary_rollups")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default(),
     }))
 }
 
@@ -127,6 +164,7 @@ fn save_snapshot(app: &AppHandle, snapshot: AppSnapshot) -> Result<(), String> {
     store.set("pet", json!(snapshot.pet));
     store.set("coins", json!(snapshot.coins));
     store.set("tasks", json!(snapshot.tasks));
+    store.set("task_lists", json!(snapshot.task_lists));
     store.set("goals", json!(snapshot.goals));
     store.set("sessions", json!(snapshot.sessions));
     store.set("settings", json!(snapshot.settings));
@@ -143,6 +181,22 @@ fn save_snapshot(app: &AppHandle, snapshot: AppSnapshot) -> Result<(), String> {
         "focus_guardrail_events",
         json!(snapshot.focus_guardrail_events),
     );
+    store.set("reward_ledger", json!(snapshot.reward_ledger));
+    store.set("goal_history", json!(snapshot.goal_history));
+    store.set("summary_rollups", json!(snapshot.summary_rollups));
+    if let Some(templates) = &snapshot.goal_templates {
+        store.set("goal_templates", json!(templates));
+    }
+    if let Some(templates) = &snapshot.period_goal_templates {
+        store.set("period_goal_templates", json!(templates));
+    }
+    // Without a history, drop the old one so it is reseeded from the restored balance.
+    match &snapshot.coin_transactions {
+        Some(transactions) => store.set("coin_transactions", json!(transactions)),
+        None => {
+            store.delete("coin_transactions");
+        }
+    }
     store.save().map_err(|e| e.to_string())?;
 
     Ok(())
@@ -208,6 +262,47 @@ pub fn get_app_diagnostics(app: AppHandle) -> Result<AppDiagnostics, String> {
     })
 }
 
+/// Recomputes progress and daily summaries from sessions, tasks and the reward ledger.
+/// Returns what would change; the result is only written back when `apply` is set.
+#[tauri::command]
+pub fn rebuild_progress(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    apply: bool,
+) -> Result<ProgressRebuildReport, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let snapshot = load_snapshot(&app)?;
+    let clock = crate::clock::Clock::from_settings(&snapshot.settings);
+    let (progress, summaries) = crate::progress_rebuild::rebuild(
+        &snapshot.progress,
+        &snapshot.summaries,
+        &snapshot.summary_rollups,
+        &snapshot.sessions,
+        &snapshot.tasks,
+        &snapshot.reward_ledger,
+        &clock,
+        snapshot.settings.streak_weekend_grace,
+        &snapshot.settings.xp_curve,
+    );
+    let changes = crate::progress_rebuild::diff(
+        &snapshot.progress,
+        &snapshot.summaries,
+        &progress,
+        &summaries,
+    );
+
+    let applied = apply && !changes.is_empty();
+    if applied {
+        let store = app.store("store.json").map_err(|e| e.to_string())?;
+        store.set("user_progress", json!(progress));
+        store.set("daily_summaries", json!(summaries));
+        let _ = app.emit(EVENT_PROFILE_CHANGED, &progress);
+        let _ = app.emit(EVENT_ANALYTICS_CHANGED, &summaries);
+    }
+
+    Ok(ProgressRebuildReport { applied, changes })
+}
+
 #[cfg(test)]
 mod tests {
     use super::sanitize_snapshot;
@@ -222,12 +317,14 @@ mod tests {
                 title: " ".to_string(),
                 completed: false,
                 created_at: "2026-01-01T00:00:00Z".to_string(),
+                ..Default::default()
             },
             Task {
                 id: "2".to_string(),
                 title: "  Keep this task  ".to_string(),
                 completed: false,
                 created_at: "2026-01-01T00:00:00Z".to_string(),
+                ..Default::default()
             },
         ];
 
@@ -1,14 +1,17 @@
 pub mod achievements;
+pub mod analytics;
 pub mod coins;
 pub mod customization;
 pub mod focus_guardrails;
 pub mod goals;
 pub mod maintenance;
+pub mod period_goals;
 pub mod pet;
 pub mod pomodoro;
 pub mod progress;
 pub mod settings;
 pub mod shop;
+pub mod task_transfer;
 pub mod tasks;
 pub mod timer_runtime;
 pub mod tray;
@@ -0,0 +1,547 @@
+use chrono::{Datelike, NaiveDate};
+use serde_json::json;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use crate::clock::Clock;
+use crate::models::{
+    DailySummary, PeriodGoal, PeriodGoalTemplate, PeriodGoalTemplatePatch, PomodoroSession,
+};
+
+const PERIOD_GOAL_TEMPLATES_KEY: &str = "period_goal_templates";
+const PERIOD_GOALS_KEY: &str = "period_goals";
+const MAX_PERIOD_GOAL_TEMPLATES: usize = 12;
+const MAX_PERIOD_COUNT_TARGET: u32 = 1_000;
+const MAX_PERIOD_FOCUS_MINUTES_TARGET: u32 = 20_000;
+const MAX_DAY_THRESHOLD_MINUTES: u32 = 720;
+const DEFAULT_DAY_THRESHOLD_MINUTES: u32 = 120;
+const ALLOWED_PERIODS: &[&str] = &["week", "month"];
+const ALLOWED_PERIOD_METRICS: &[&str] = &[
+    "pomodoros",
+    "focus_minutes",
+    "tasks",
+    "breaks",
+    "focus_days",
+];
+
+fn template(id: &str, period: &str, metric: &str, target: u32) -> PeriodGoalTemplate {
+    PeriodGoalTemplate {
+        id: id.to_string(),
+        period: period.to_string(),
+        metric: metric.to_string(),
+        target,
+        day_threshold_minutes: if metric == "focus_days" {
+            DEFAULT_DAY_THRESHOLD_MINUTES
+        } else {
+            0
+        },
+        enabled: true,
+    }
+}
+
+fn default_period_goal_templates() -> Vec<PeriodGoalTemplate> {
+    vec![
+        template("weekly_pomodoros", "week", "pomodoros", 20),
+        template("weekly_focus_days", "week", "focus_days", 5),
+        template("monthly_pomodoros", "month", "pomodoros", 80),
+    ]
+}
+
+fn max_period_target(period: &str, metric: &str) -> u32 {
+    match (period, metric) {
+        ("week", "focus_days") => 7,
+        (_, "focus_days") => 31,
+        (_, "focus_minutes") => MAX_PERIOD_FOCUS_MINUTES_TARGET,
+        _ => MAX_PERIOD_COUNT_TARGET,
+    }
+}
+
+fn format_minutes(minutes: u32) -> String {
+    if minutes % 60 == 0 {
+        let hours = minutes / 60;
+        format!("{} hour{}", hours, if hours == 1 { "" } else { "s" })
+    } else {
+        format!("{} minute{}", minutes, if minutes == 1 { "" } else { "s" })
+    }
+}
+
+fn period_goal_description(template: &PeriodGoalTemplate) -> String {
+    let period = if template.period == "week" {
+        "this week"
+    } else {
+        "this month"
+    };
+    if template.metric == "focus_days" {
+        format!(
+            "{} day{} with at least {} of focus {}",
+            template.target,
+            if template.target == 1 { "" } else { "s" },
+            format_minutes(template.day_threshold_minutes),
+            period
+        )
+    } else {
+        format!(
+            "{} {}",
+            crate::commands::goals::goal_description(&template.metric, template.target),
+            period
+        )
+    }
+}
+
+fn validate_period_goal_template(template: &mut PeriodGoalTemplate) -> Result<(), String> {
+    template.period = template.period.trim().to_ascii_lowercase();
+    if !ALLOWED_PERIODS.contains(&template.period.as_str()) {
+        return Err(format!("Invalid goal period: {}", template.period));
+    }
+    template.metric = template.metric.trim().to_ascii_lowercase();
+    if !ALLOWED_PERIOD_METRICS.contains(&template.metric.as_str()) {
+        return Err(format!("Invalid goal metric: {}", template.metric));
+    }
+    let max_target = max_period_target(&template.period, &template.metric);
+    if template.target == 0 || template.target > max_target {
+        return Err(format!("Goal target must be between 1 and {}", max_target));
+    }
+    if template.metric == "focus_days" {
+        if template.day_threshold_minutes == 0
+            || template.day_threshold_minutes > MAX_DAY_THRESHOLD_MINUTES
+        {
+            return Err(format!(
+                "Daily focus threshold must be between 1 and {} minutes",
+                MAX_DAY_THRESHOLD_MINUTES
+            ));
+        }
+    } else {
+        template.day_threshold_minutes = 0;
+    }
+    Ok(())
+}
+
+/// Drops invalid or duplicate templates and clamps targets, for stored or imported data.
+pub(crate) fn sanitize_period_goal_templates(templates: &mut Vec<PeriodGoalTemplate>) {
+    let mut seen: Vec<String> = Vec::new();
+    templates.retain_mut(|template| {
+        if template.id.is_empty() || seen.contains(&template.id) {
+            return false;
+        }
+        if template.metric == "focus_days" {
+            template.day_threshold_minutes = template
+                .day_threshold_minutes
+                .clamp(1, MAX_DAY_THRESHOLD_MINUTES);
+        }
+        template.target = template
+            .target
+            .clamp(1, max_period_target(&template.period, &template.metric));
+        if validate_period_goal_template(template).is_err() {
+            return false;
+        }
+        seen.push(template.id.clone());
+        true
+    });
+    templates.truncate(MAX_PERIOD_GOAL_TEMPLATES);
+}
+
+/// Inclusive first and last day of the period containing `date`.
+pub(crate) fn period_bounds(
+    period: &str,
+    date: NaiveDate,
+    week_start_day: u8,
+) -> (NaiveDate, NaiveDate) {
+    if period == "week" {
+        let offset = (date.weekday().num_days_from_monday() + 7 - u32::from(week_start_day)) % 7;
+        let start = date - chrono::Duration::days(i64::from(offset));
+        (start, start + chrono::Duration::days(6))
+    } else {
+        let start = date.with_day(1).unwrap_or(date);
+        let next_month = if start.month() == 12 {
+            NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(start.year(), start.month() + 1, 1)
+        };
+        let end = next_month
+            .map(|next| next - chrono::Duration::days(1))
+            .unwrap_or(start);
+        (start, end)
+    }
+}
+
+/// Local completion day and focus minutes of each completed session.
+fn session_days(sessions: &[PomodoroSession], clock: &Clock) -> Vec<(String, u32)> {
+    sessions
+        .iter()
+        .filter_map(|session| {
+            let day = clock.day_of_timestamp(session.completed_at.as_deref()?)?;
+            Some((day, session.work_duration / 60))
+        })
+        .collect()
+}
+
+fn evaluate_metric(
+    template: &PeriodGoalTemplate,
+    start: &str,
+    end: &str,
+    sessions: &[(String, u32)],
+    summaries: &[DailySummary],
+) -> u32 {
+    let in_period = |date: &str| date >= start && date <= end;
+    let completed_sessions = || sessions.iter().filter(|(day, _)| in_period(day));
+    let period_summaries = || summaries.iter().filter(|summary| in_period(&summary.date));
+
+    match template.metric.as_str() {
+        "pomodoros" => completed_sessions().count() as u32,
+        "focus_minutes" => completed_sessions().map(|(_, minutes)| minutes).sum(),
+        "tasks" => period_summaries().map(|s| s.tasks_completed).sum(),
+        "breaks" => period_summaries().map(|s| s.breaks_taken).sum(),
+        _ => period_summaries()
+            .filter(|s| s.focus_minutes >= template.day_threshold_minutes)
+            .count() as u32,
+    }
+}
+
+fn build_period_goals(
+    templates: &[PeriodGoalTemplate],
+    today: NaiveDate,
+    week_start_day: u8,
+    sessions: &[(String, u32)],
+    summaries: &[DailySummary],
+    previous: &[PeriodGoal],
+) -> Vec<PeriodGoal> {
+    templates
+        .iter()
+        .filter(|template| template.enabled)
+        .map(|template| {
+            let (start, end) = period_bounds(&template.period, today, week_start_day);
+            let start = start.format("%Y-%m-%d").to_string();
+            let end = end.format("%Y-%m-%d").to_string();
+            let progress = evaluate_metric(template, &start, &end, sessions, summaries);
+            let completed_at = previous
+                .iter()
+                .find(|goal| goal.id == template.id && goal.period_start == start)
+                .and_then(|goal| goal.completed_at.clone());
+            PeriodGoal {
+                id: template.id.clone(),
+                period: template.period.clone(),
+                metric: template.metric.clone(),
+                description: period_goal_description(template),
+                target: template.target,
+                progress: progress.min(template.target),
+                period_start: start,
+                period_end: end,
+                completed_at,
+            }
+        })
+        .collect()
+}
+
+fn load_period_goal_templates(app: &AppHandle) -> Result<Vec<PeriodGoalTemplate>, String> {
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    let mut templates = store
+        .get(PERIOD_GOAL_TEMPLATES_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_else(default_period_goal_templates);
+    sanitize_period_goal_templates(&mut templates);
+    Ok(templates)
+}
+
+fn save_period_goal_templates(
+    app: &AppHandle,
+    templates: &[PeriodGoalTemplate],
+) -> Result<(), String> {
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    store.set(PERIOD_GOAL_TEMPLATES_KEY, json!(templates));
+    Ok(())
+}
+
+fn period_goal_xp(period: &str) -> u32 {
+    if period == "week" {
+        crate::progression::XP_PER_WEEKLY_GOAL_COMPLETION
+    } else {
+        crate::progression::XP_PER_MONTHLY_GOAL_COMPLETION
+    }
+}
+
+/// Rewards are keyed by what the goal asks for rather than its template id, so recreating
+/// a template doesn't pay the same period's goal again.
+fn period_goal_reward_source_id(goal: &PeriodGoal) -> String {
+    format!(
+        "{}:{}:{}:{}",
+        goal.period_start, goal.period, goal.metric, goal.target
+    )
+}
+
+/// Recomputes weekly and monthly goals from sessions and summaries, rolling over to the
+/// current period and rewarding goals completed since the last refresh.
+pub(crate) fn refresh_period_goals(app: &AppHandle) -> Result<Vec<PeriodGoal>, String> {
+    let settings = crate::commands::settings::get_settings(app.clone())?;
+    let clock = Clock::from_settings(&settings);
+    let today = clock.today();
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    let sessions: Vec<PomodoroSession> = store
+        .get("sessions")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    let summaries = crate::progression::get_daily_summaries(app, 62)?;
+    let previous: Vec<PeriodGoal> = store
+        .get(PERIOD_GOALS_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+
+    let mut goals = build_period_goals(
+        &load_period_goal_templates(app)?,
+        today,
+        settings.week_start_day,
+        &session_days(&sessions, &clock),
+        &summaries,
+        &previous,
+    );
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut completed = Vec::new();
+    for goal in goals.iter_mut() {
+        if goal.completed_at.is_none() && goal.progress >= goal.target {
+            goal.completed_at = Some(now.clone());
+            completed.push(goal.clone());
+        }
+    }
+
+    if goals != previous {
+        store.set(PERIOD_GOALS_KEY, json!(goals));
+        let _ = crate::commands::goals::emit_goals_changed(app);
+    }
+    for goal in &completed {
+        let xp = period_goal_xp(&goal.period);
+        if crate::rewards::claim(
+            app,
+            crate::rewards::SOURCE_PERIOD_GOAL,
+            &period_goal_reward_source_id(goal),
+            crate::rewards::REWARD_XP,
+            xp,
+        )? {
+            crate::progression::record_period_goal_completion(app, xp)?;
+        }
+    }
+    Ok(goals)
+}
+
+#[tauri::command]
+pub fn get_period_goals(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+) -> Result<Vec<PeriodGoal>, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    refresh_period_goals(&app)
+}
+
+#[tauri::command]
+pub fn get_period_goal_templates(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+) -> Result<Vec<PeriodGoalTemplate>, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    load_period_goal_templates(&app)
+}
+
+#[tauri::command]
+pub fn create_period_goal_template(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    period: String,
+    metric: String,
+    target: u32,
+    day_threshold_minutes: Option<u32>,
+) -> Result<PeriodGoalTemplate, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let mut templates = load_period_goal_templates(&app)?;
+    if templates.len() >= MAX_PERIOD_GOAL_TEMPLATES {
+        return Err(format!(
+            "Cannot have more than {} weekly or monthly goals",
+            MAX_PERIOD_GOAL_TEMPLATES
+        ));
+    }
+    let mut template = PeriodGoalTemplate {
+        id: uuid::Uuid::new_v4().to_string(),
+        period,
+        metric,
+        target,
+        day_threshold_minutes: day_threshold_minutes.unwrap_or(DEFAULT_DAY_THRESHOLD_MINUTES),
+        enabled: true,
+    };
+    validate_period_goal_template(&mut template)?;
+    templates.push(template.clone());
+    save_period_goal_templates(&app, &templates)?;
+    refresh_period_goals(&app)?;
+    Ok(template)
+}
+
+#[tauri::command]
+pub fn update_period_goal_template(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    template_id: String,
+    patch: PeriodGoalTemplatePatch,
+) -> Result<PeriodGoalTemplate, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let mut templates = load_period_goal_templates(&app)?;
+    let template = templates
+        .iter_mut()
+        .find(|t| t.id == template_id)
+        .ok_or_else(|| "Goal template not found".to_string())?;
+    let mut updated = template.clone();
+    patch.apply_to(&mut updated);
+    validate_period_goal_template(&mut updated)?;
+    *template = updated.clone();
+    save_period_goal_templates(&app, &templates)?;
+    refresh_period_goals(&app)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+pub fn delete_period_goal_template(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    template_id: String,
+) -> Result<Vec<PeriodGoalTemplate>, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let mut templates = load_period_goal_templates(&app)?;
+    let before = templates.len();
+    templates.retain(|t| t.id != template_id);
+    if templates.len() == before {
+        return Err("Goal template not found".to_string());
+    }
+    save_period_goal_templates(&app, &templates)?;
+    refresh_period_goals(&app)?;
+    Ok(templates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn session(completed_at: &str, minutes: u32) -> PomodoroSession {
+        PomodoroSession {
+            id: completed_at.to_string(),
+            started_at: completed_at.to_string(),
+            completed_at: Some(completed_at.to_string()),
+            work_duration: minutes * 60,
+            break_duration: 300,
+            break_completed_at: None,
+        }
+    }
+
+    fn focus_summary(day: &str, minutes: u32) -> DailySummary {
+        DailySummary {
+            date: day.to_string(),
+            focus_minutes: minutes,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn week_bounds_follow_configured_start_day() {
+        // 2026-03-11 is a Wednesday.
+        let (start, end) = period_bounds("week", date("2026-03-11"), 0);
+        assert_eq!((start, end), (date("2026-03-09"), date("2026-03-15")));
+        let (start, end) = period_bounds("week", date("2026-03-11"), 6);
+        assert_eq!((start, end), (date("2026-03-08"), date("2026-03-14")));
+        let (start, _) = period_bounds("week", date("2026-03-08"), 6);
+        assert_eq!(start, date("2026-03-08"));
+    }
+
+    #[test]
+    fn month_bounds_cover_whole_month() {
+        assert_eq!(
+            period_bounds("month", date("2026-02-17"), 0),
+            (date("2026-02-01"), date("2026-02-28"))
+        );
+        assert_eq!(
+            period_bounds("month", date("2026-12-31"), 0),
+            (date("2026-12-01"), date("2026-12-31"))
+        );
+    }
+
+    #[test]
+    fn period_goals_are_evaluated_from_sessions_and_summaries() {
+        let sessions = vec![
+            session("2026-03-09T09:00:00Z", 25),
+            session("2026-03-10T09:00:00Z", 50),
+            session("2026-03-08T09:00:00Z", 25),
+        ];
+        let summaries = vec![
+            focus_summary("2026-03-09", 130),
+            focus_summary("2026-03-10", 60),
+            focus_summary("2026-03-11", 120),
+        ];
+        let goals = build_period_goals(
+            &default_period_goal_templates(),
+            date("2026-03-11"),
+            0,
+            &session_days(&sessions, &Clock::new(Some(chrono_tz::UTC), 0)),
+            &summaries,
+            &[],
+        );
+        assert_eq!(goals.len(), 3);
+        assert_eq!(goals[0].progress, 2);
+        assert_eq!(goals[0].period_start, "2026-03-09");
+        assert_eq!(goals[1].progress, 2);
+        assert_eq!(
+            goals[1].description,
+            "5 days with at least 2 hours of focus this week"
+        );
+        assert_eq!(goals[2].progress, 3);
+        assert_eq!(goals[2].period_end, "2026-03-31");
+    }
+
+    #[test]
+    fn completion_carries_over_only_within_same_period() {
+        let templates = vec![template("weekly_pomodoros", "week", "pomodoros", 1)];
+        let previous = vec![PeriodGoal {
+            id: "weekly_pomodoros".to_string(),
+            period: "week".to_string(),
+            metric: "pomodoros".to_string(),
+            description: String::new(),
+            target: 1,
+            progress: 1,
+            period_start: "2026-03-02".to_string(),
+            period_end: "2026-03-08".to_string(),
+            completed_at: Some("2026-03-03T10:00:00Z".to_string()),
+        }];
+        let same_week = build_period_goals(&templates, date("2026-03-05"), 0, &[], &[], &previous);
+        assert!(same_week[0].completed_at.is_some());
+        let next_week = build_period_goals(&templates, date("2026-03-10"), 0, &[], &[], &previous);
+        assert!(next_week[0].completed_at.is_none());
+    }
+
+    #[test]
+    fn validate_period_goal_template_rejects_bad_input() {
+        let mut weekly_days = template("x", "week", "focus_days", 8);
+        assert!(validate_period_goal_template(&mut weekly_days).is_err());
+        let mut bad_period = template("x", "year", "pomodoros", 8);
+        assert!(validate_period_goal_template(&mut bad_period).is_err());
+        let mut monthly = template("x", " Month ", "tasks", 30);
+        monthly.day_threshold_minutes = 90;
+        validate_period_goal_template(&mut monthly).unwrap();
+        assert_eq!(monthly.period, "month");
+        assert_eq!(monthly.day_threshold_minutes, 0);
+    }
+
+    #[test]
+    fn period_goal_reward_source_id_ignores_the_template_id() {
+        let goal = PeriodGoal {
+            id: "0b7e-template".to_string(),
+            period: "week".to_string(),
+            metric: "pomodoros".to_string(),
+            description: String::new(),
+            target: 20,
+            progress: 20,
+            period_start: "2026-03-02".to_string(),
+            period_end: "2026-03-08".to_string(),
+            completed_at: None,
+        };
+        assert_eq!(
+            period_goal_reward_source_id(&goal),
+            "2026-03-02:week:pomodoros:20"
+        );
+    }
+}
@@ -2,12 +2,14 @@ use serde_json::json;
 use tauri::{AppHandle, Emitter};
 use tauri_plugin_store::StoreExt;
 
-use crate::events::{EVENT_COINS_CHANGED, EVENT_PET_STATE_CHANGED};
-use crate::models::{CoinBalance, PetEvent, PetQuest, PetState};
+use crate::events::EVENT_PET_STATE_CHANGED;
+use crate::models::{PetEvent, PetQuest, PetState};
 
 const MAX_PET_EVENTS: usize = 30;
 const MAX_SPECIES_ID_CHARS: usize = 48;
 const QUEST_ROLL_COOLDOWN_SECS: i64 = 20 * 60;
+/// Coins charged for swapping the active quest for a new one.
+pub const QUEST_REROLL_COST: u32 = 15;
 const QUEST_LAST_KIND_KEY: &str = "pet_last_quest_kind";
 const QUEST_LAST_ROLL_KEY: &str = "pet_last_quest_roll_at";
 const QUEST_RECENT_FOCUS_KEY: &str = "pet_recent_focus_progress";
@@ -117,10 +119,11 @@ fn apply_care_decay(mut pet: PetState) -> PetState {
 
 fn load_pet(app: &AppHandle) -> Result<PetState, String> {
     let store = app.store("store.json").map_err(|e| e.to_string())?;
-    let pet: PetState = store
+    let mut pet: PetState = store
         .get("pet")
         .and_then(|v| serde_json::from_value(v).ok())
         .unwrap_or_default();
+    crate::commands::shop::normalize_pet_items(&mut pet);
     Ok(apply_care_decay(pet))
 }
 
@@ -130,6 +133,30 @@ fn save_pet(app: &AppHandle, pet: &PetState) -> Result<(), String> {
     Ok(())
 }
 
+/// Boosts affection and logs a celebration once every daily goal is complete.
+pub(crate) fn celebrate_perfect_day(
+    app: &AppHandle,
+    affection_boost: u32,
+    bonus_coins: u32,
+) -> Result<PetState, String> {
+    let mut pet = load_pet(app)?;
+    pet.affection = (pet.affection + affection_boost).min(100);
+    pet.animation_state = "celebrating".to_string();
+    save_pet(app, &pet)?;
+    let _ = app.emit(EVENT_PET_STATE_CHANGED, &pet);
+
+    let description = if bonus_coins > 0 {
+        format!(
+            "Perfect day! Every goal complete: +{} coins, affection {}%",
+            bonus_coins, pet.affection
+        )
+    } else {
+        format!("Perfect day! Every goal complete, affection {}%", pet.affection)
+    };
+    append_event(app, "perfect_day", description, true)?;
+    Ok(pet)
+}
+
 fn quest_target_for_stage(stage: u32) -> u32 {
     match stage {
         0 => 1,
@@ -262,14 +289,11 @@ fn quest_mix_score(kind: &str, recent_focus: u32, recent_care: u32) -> i32 {
     }
 }
 
-fn quest_accessory_score(kind: &str, accessories: &[String]) -> i32 {
+/// Scores the accessories the pet is wearing; owned but unworn ones don't count.
+fn quest_accessory_score(kind: &str, equipped: &std::collections::BTreeMap<String, String>) -> i32 {
     let mut score = 0;
-    for accessory in accessories {
+    for accessory in equipped.values() {
         score += match accessory.as_str() {
-            "apple" | "cookie" => match kind {
-                "care_actions" | "mindful_reset" => 1,
-                _ => 0,
-            },
             "party_hat" | "bow_tie" => match kind {
                 "focus_sessions" | "balanced_routine" => 1,
                 _ => 0,
@@ -284,6 +308,15 @@ fn quest_accessory_score(kind: &str, accessories: &[String]) -> i32 {
     score.min(3)
 }
 
+/// A stocked pantry nudges quest selection towards care, as owning food used to.
+fn quest_pantry_score(kind: &str, inventory: &std::collections::BTreeMap<String, u32>) -> i32 {
+    let stocked = inventory.values().any(|units| *units > 0);
+    match kind {
+        "care_actions" | "mindful_reset" if stocked => 2,
+        _ => 0,
+    }
+}
+
 fn quest_total_score(
     template: QuestTemplate,
     pet: &PetState,
@@ -293,7 +326,8 @@ fn quest_total_score(
 ) -> i32 {
     let mut score = quest_stage_score(template.kind, pet.current_stage)
         + quest_mix_score(template.kind, recent_focus, recent_care)
-        + quest_accessory_score(template.kind, &pet.accessories);
+        + quest_accessory_score(template.kind, &pet.equipped)
+        + quest_pantry_score(template.kind, &pet.inventory);
     if Some(template.kind) == last_quest_kind {
         score -= 3;
     }
@@ -546,11 +580,7 @@ pub fn pet_interact(
             pet.energy = clamp_metric(pet.energy as i32 - 1);
             pet.animation_state = "clicked".to_string();
         }
-        "feed" => {
-            pet.hunger = clamp_metric(pet.hunger as i32 - 25);
-            pet.affection = clamp_metric(pet.affection as i32 + 4);
-            pet.animation_state = "celebrating".to_string();
-        }
+        "feed" => feed_pet(&mut pet),
         "play" => {
             pet.affection = clamp_metric(pet.affection as i32 + 10);
             pet.energy = clamp_metric(pet.energy as i32 - 8);
@@ -576,7 +606,48 @@ pub fn pet_interact(
         _ => return Err("Unknown pet interaction".to_string()),
     }
 
-    pet.last_interaction = Some(action.clone());
+    finish_interaction(&app, pet, &action)
+}
+
+/// Feeds one unit of a food item from the inventory: the regular feed plus the item's effects.
+#[tauri::command]
+pub fn feed_item(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    item_id: String,
+) -> Result<PetState, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let effects = crate::commands::shop::food_effects(&item_id)
+        .ok_or_else(|| format!("Not a food item: {}", item_id))?;
+    let mut pet = load_pet(&app)?;
+    if !crate::commands::shop::take_from_inventory(&mut pet, &item_id) {
+        return Err(format!("No {} left in the inventory", item_id));
+    }
+
+    feed_pet(&mut pet);
+    apply_food_effects(&mut pet, effects);
+    finish_interaction(&app, pet, "feed")
+}
+
+fn feed_pet(pet: &mut PetState) {
+    pet.hunger = clamp_metric(pet.hunger as i32 - 25);
+    pet.affection = clamp_metric(pet.affection as i32 + 4);
+    pet.animation_state = "celebrating".to_string();
+}
+
+fn apply_food_effects(pet: &mut PetState, effects: crate::commands::shop::FoodEffects) {
+    pet.hunger = clamp_metric(pet.hunger as i32 + effects.hunger);
+    pet.affection = clamp_metric(pet.affection as i32 + effects.affection);
+    pet.energy = clamp_metric(pet.energy as i32 + effects.energy);
+}
+
+/// Shared tail of every interaction: logs it, saves the pet and advances care progress.
+fn finish_interaction(
+    app: &AppHandle,
+    mut pet: PetState,
+    action: &str,
+) -> Result<PetState, String> {
+    pet.last_interaction = Some(action.to_string());
     pet.last_care_update_at = chrono::Utc::now().to_rfc3339();
     pet = apply_care_decay(pet);
 
@@ -584,11 +655,12 @@ pub fn pet_interact(
         "Action '{}' -> mood {}, energy {}%, hunger {}%, affection {}%",
         action, pet.mood, pet.energy, pet.hunger, pet.affection
     );
-    let _ = append_event(&app, "interaction", event_message, true);
+    let _ = append_event(app, "interaction", event_message, true);
 
-    save_pet(&app, &pet)?;
+    save_pet(app, &pet)?;
     let _ = app.emit(EVENT_PET_STATE_CHANGED, &pet);
-    let _ = advance_care_quest(&app, 1);
+    let _ = advance_care_quest(app, 1);
+    let _ = crate::commands::goals::increment_goal_progress(app, "care_actions");
     Ok(pet)
 }
 
@@ -602,10 +674,14 @@ pub fn set_pet_customization(
     let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
     let mut pet = load_pet(&app)?;
     if let Some(skin) = skin {
-        pet.skin = validate_variant(skin, ALLOWED_SKINS, "pet skin")?;
+        let skin = validate_variant(skin, ALLOWED_SKINS, "pet skin")?;
+        crate::leveling::ensure_cosmetic_unlocked(&app, "skin", &skin, &pet.skin)?;
+        pet.skin = skin;
     }
     if let Some(scene) = scene {
-        pet.scene = validate_variant(scene, ALLOWED_SCENES, "pet scene")?;
+        let scene = validate_variant(scene, ALLOWED_SCENES, "pet scene")?;
+        crate::leveling::ensure_cosmetic_unlocked(&app, "scene", &scene, &pet.scene)?;
+        pet.scene = scene;
     }
     save_pet(&app, &pet)?;
     let _ = app.emit(EVENT_PET_STATE_CHANGED, &pet);
@@ -646,6 +722,35 @@ pub fn get_pet_active_quest(app: AppHandle) -> Result<Option<PetQuest>, String>
     load_active_quest(&app)
 }
 
+/// Swaps the active quest for a freshly picked one and charges `QUEST_REROLL_COST`.
+#[tauri::command]
+pub fn reroll_quest(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+) -> Result<PetQuest, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let current = load_active_quest(&app)?.ok_or_else(|| "No active quest to reroll".to_string())?;
+    let pet = load_pet(&app)?;
+    let (recent_focus, recent_care) = load_recent_quest_progress(&app)?;
+    let quest = create_quest_for_pet(&pet, Some(&current.kind), recent_focus, recent_care);
+
+    crate::commands::coins::debit_coins(
+        &app,
+        QUEST_REROLL_COST,
+        crate::commands::coins::COIN_SOURCE_QUEST_REROLL,
+        Some(&current.id),
+    )?;
+    save_active_quest(&app, Some(&quest))?;
+    save_last_quest_kind(&app, &quest.kind)?;
+    let _ = append_event(
+        &app,
+        "quest",
+        format!("New quest: {} (0/{})", quest.title, quest.target_sessions),
+        false,
+    );
+    Ok(quest)
+}
+
 #[tauri::command]
 pub fn resolve_pet_event(
     app: AppHandle,
@@ -705,15 +810,22 @@ fn progress_active_quest(
     }
 
     save_active_quest(app, None)?;
-
-    let store = app.store("store.json").map_err(|e| e.to_string())?;
-    let mut coins: CoinBalance = store
-        .get("coins")
-        .and_then(|v| serde_json::from_value(v).ok())
-        .unwrap_or_default();
-    coins.total += quest.reward_coins;
-    store.set("coins", json!(coins));
-    let _ = app.emit(EVENT_COINS_CHANGED, &coins);
+    let _ = crate::commands::goals::increment_goal_progress(app, "quests");
+
+    if crate::rewards::claim(
+        app,
+        crate::rewards::SOURCE_QUEST,
+        &quest.id,
+        crate::rewards::REWARD_COINS,
+        quest.reward_coins,
+    )? {
+        crate::commands::coins::credit_coins(
+            app,
+            quest.reward_coins,
+            crate::commands::coins::COIN_SOURCE_QUEST,
+            Some(&quest.id),
+        )?;
+    }
 
     let mut pet = load_pet(app)?;
     pet.affection = clamp_metric(pet.affection as i32 + 12);
@@ -967,7 +1079,7 @@ mod tests {
     fn quest_selection_accounts_for_accessory_bias() {
         let mut pet = PetState::default();
         pet.current_stage = 2;
-        pet.accessories = vec!["apple".to_string(), "cookie".to_string()];
+        pet.inventory.insert("apple".to_string(), 2);
         let quest = create_quest_for_pet(&pet, None, 1, 1);
         assert!(quest.kind == "care_actions" || quest.kind == "mindful_reset");
     }
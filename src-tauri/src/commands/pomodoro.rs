@@ -1,18 +1,20 @@
 use serde_json::json;
-use chrono::Timelike;
 use tauri::{AppHandle, Emitter};
 use tauri_plugin_store::StoreExt;
 
-use crate::events::{EVENT_COINS_CHANGED, EVENT_PET_STATE_CHANGED};
-use crate::models::{CoinBalance, PetState, PomodoroSession, UserProgress};
+use crate::events::EVENT_PET_STATE_CHANGED;
+use crate::models::{PetState, PomodoroSession, UserProgress};
 
-const COINS_PER_POMODORO: u32 = 10;
+pub(crate) const COINS_PER_POMODORO: u32 = 10;
 const DEFAULT_STAGE_1_THRESHOLD: u32 = 5;
 const DEFAULT_STAGE_2_THRESHOLD: u32 = 15;
 const MIN_WORK_DURATION_SECS: u32 = 5 * 60;
 const MAX_WORK_DURATION_SECS: u32 = 2 * 60 * 60;
 const MIN_BREAK_DURATION_SECS: u32 = 60;
 const MAX_BREAK_DURATION_SECS: u32 = 30 * 60;
+/// Share of the planned break that must pass before it counts as taken.
+const MIN_HONOURED_BREAK_PERCENT: i64 = 80;
+const BREAK_ENERGY_RESTORE: u32 = 8;
 
 fn clamp_work_duration(seconds: u32) -> u32 {
     seconds.clamp(MIN_WORK_DURATION_SECS, MAX_WORK_DURATION_SECS)
@@ -60,6 +62,7 @@ pub fn start_pomodoro(
         completed_at: None,
         work_duration,
         break_duration,
+        break_completed_at: None,
     };
 
     let mut sessions: Vec<PomodoroSession> = store
@@ -105,14 +108,24 @@ pub async fn complete_pomodoro(
     let completed_work_duration = session.work_duration;
     store.set("sessions", json!(sessions));
 
-    // Award coins
-    let mut coins: CoinBalance = store
-        .get("coins")
-        .and_then(|v| serde_json::from_value(v).ok())
-        .unwrap_or_default();
-    coins.total += COINS_PER_POMODORO;
-    store.set("coins", json!(coins));
-    let _ = app.emit(EVENT_COINS_CHANGED, &coins);
+    // Award coins once per session
+    let coins_awarded = if crate::rewards::claim(
+        &app,
+        crate::rewards::SOURCE_SESSION,
+        &session_id,
+        crate::rewards::REWARD_COINS,
+        COINS_PER_POMODORO,
+    )? {
+        crate::commands::coins::credit_coins(
+            &app,
+            COINS_PER_POMODORO,
+            crate::commands::coins::COIN_SOURCE_POMODORO,
+            Some(&session_id),
+        )?;
+        COINS_PER_POMODORO
+    } else {
+        0
+    };
 
     // Update pet: increment pomodoros, check evolution
     let mut pet: PetState = store
@@ -155,27 +168,133 @@ pub async fn complete_pomodoro(
     let _ = app.emit(EVENT_PET_STATE_CHANGED, &pet);
 
     // Update daily goal for pomodoros
-    let _ = crate::commands::goals::increment_goal_progress(&app, "pomodoros");
-    let _ = crate::commands::goals::add_goal_progress(
+    if crate::rewards::claim(
         &app,
-        "focus_minutes",
-        completed_work_duration / 60,
-    );
-    let _ =
-        crate::progression::record_focus_session(&app, completed_work_duration, COINS_PER_POMODORO);
+        crate::rewards::SOURCE_SESSION,
+        &session_id,
+        crate::rewards::REWARD_GOAL_PROGRESS,
+        1,
+    )
+    .unwrap_or(false)
+    {
+        let _ = crate::commands::goals::increment_goal_progress(&app, "pomodoros");
+        let _ = crate::commands::goals::add_goal_progress(
+            &app,
+            "focus_minutes",
+            completed_work_duration / 60,
+        );
+    }
+    if crate::rewards::claim(
+        &app,
+        crate::rewards::SOURCE_SESSION,
+        &session_id,
+        crate::rewards::REWARD_XP,
+        crate::progression::XP_PER_POMODORO,
+    )
+    .unwrap_or(false)
+    {
+        let _ =
+            crate::progression::record_focus_session(&app, completed_work_duration, coins_awarded);
+    }
     let _ = crate::commands::pet::advance_focus_quest(&app, 1);
+    let _ = crate::commands::period_goals::refresh_period_goals(&app);
 
     // Release the store lock before invoking nested commands that lock the store.
     drop(_guard);
 
     // Check for achievement unlocks
-    let completion_hour = chrono::Local::now().hour();
+    let completion_hour = crate::clock::local_hour(&app);
     let _ = crate::commands::achievements::check_achievement_progress(app.clone(), store_lock.clone()).await;
     let _ = crate::commands::achievements::check_time_achievement(app, store_lock, completion_hour).await;
 
     Ok(pet)
 }
 
+/// A break counts once most of it has elapsed and no other session started during it.
+fn break_was_honoured(
+    work_completed_at: &str,
+    break_duration: u32,
+    other_session_starts: &[&str],
+    now: chrono::DateTime<chrono::Utc>,
+) -> bool {
+    let Ok(completed_at) = chrono::DateTime::parse_from_rfc3339(work_completed_at) else {
+        return false;
+    };
+    let completed_at = completed_at.with_timezone(&chrono::Utc);
+    let interrupted = other_session_starts.iter().any(|started_at| {
+        chrono::DateTime::parse_from_rfc3339(started_at)
+            .map(|started_at| started_at >= completed_at && started_at < now)
+            .unwrap_or(false)
+    });
+    let elapsed = now.signed_duration_since(completed_at).num_seconds();
+    !interrupted && elapsed * 100 >= i64::from(break_duration) * MIN_HONOURED_BREAK_PERCENT
+}
+
+/// Records the break that followed `session_id` once enough of it has elapsed.
+#[tauri::command]
+pub fn complete_break(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    session_id: String,
+) -> Result<PetState, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+
+    let mut sessions: Vec<PomodoroSession> = store
+        .get("sessions")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    let other_session_starts: Vec<String> = sessions
+        .iter()
+        .filter(|s| s.id != session_id)
+        .map(|s| s.started_at.clone())
+        .collect();
+    let other_session_starts: Vec<&str> = other_session_starts.iter().map(String::as_str).collect();
+    let session = sessions
+        .iter_mut()
+        .find(|s| s.id == session_id)
+        .ok_or_else(|| "Session not found".to_string())?;
+    let Some(work_completed_at) = session.completed_at.clone() else {
+        return Err("Session has not been completed yet".to_string());
+    };
+    if session.break_completed_at.is_some() {
+        return Err("Break already completed".to_string());
+    }
+    let now = chrono::Utc::now();
+    if !break_was_honoured(
+        &work_completed_at,
+        session.break_duration,
+        &other_session_starts,
+        now,
+    ) {
+        return Err("Break ended too early to count".to_string());
+    }
+    session.break_completed_at = Some(now.to_rfc3339());
+    store.set("sessions", json!(sessions));
+
+    let mut pet: PetState = store
+        .get("pet")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    if crate::rewards::claim(
+        &app,
+        crate::rewards::SOURCE_BREAK,
+        &session_id,
+        crate::rewards::REWARD_GOAL_PROGRESS,
+        1,
+    )? {
+        let _ = crate::commands::goals::increment_goal_progress(&app, "breaks");
+        let _ = crate::progression::record_break_taken(&app);
+        pet.energy = (pet.energy + BREAK_ENERGY_RESTORE).min(100);
+    }
+    let _ = crate::commands::period_goals::refresh_period_goals(&app);
+    pet.animation_state = "idle".to_string();
+    store.set("pet", json!(pet));
+    let _ = app.emit(EVENT_PET_STATE_CHANGED, &pet);
+
+    Ok(pet)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -249,6 +368,29 @@ mod tests {
         assert_eq!(new_stage, 2);
     }
 
+    #[test]
+    fn break_counts_only_after_most_of_its_duration() {
+        let now = chrono::DateTime::parse_from_rfc3339("2026-01-01T10:05:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert!(break_was_honoured("2026-01-01T10:00:00Z", 300, &[], now));
+        assert!(break_was_honoured("2026-01-01T10:01:00Z", 300, &[], now));
+        assert!(!break_was_honoured("2026-01-01T10:02:00Z", 300, &[], now));
+        assert!(!break_was_honoured("not a date", 300, &[], now));
+    }
+
+    #[test]
+    fn break_is_void_when_another_session_started_during_it() {
+        let now = chrono::DateTime::parse_from_rfc3339("2026-01-01T10:05:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let work_done = "2026-01-01T10:00:00Z";
+        let earlier = ["2026-01-01T09:35:00Z"];
+        assert!(break_was_honoured(work_done, 300, &earlier, now));
+        let overlapping = ["2026-01-01T09:35:00Z", "2026-01-01T10:02:00Z"];
+        assert!(!break_was_honoured(work_done, 300, &overlapping, now));
+    }
+
     #[test]
     fn evolution_logic_supports_species_thresholds() {
         assert_eq!(stage_for_total_pomodoros(9, &[0, 8, 20]), 1);
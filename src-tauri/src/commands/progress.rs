@@ -7,6 +7,15 @@ pub fn get_user_progress(app: AppHandle) -> Result<UserProgress, String> {
     crate::progression::get_user_progress(&app)
 }
 
+#[tauri::command]
+pub fn buy_streak_freeze(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+) -> Result<UserProgress, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    crate::progression::buy_streak_freeze(&app)
+}
+
 #[tauri::command]
 pub fn get_daily_summaries(app: AppHandle, days: Option<u32>) -> Result<Vec<DailySummary>, String> {
     crate::progression::get_daily_summaries(&app, days.unwrap_or(14).max(1).min(90))
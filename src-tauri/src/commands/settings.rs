@@ -25,6 +25,12 @@ const MAX_SEASONAL_PACKS: usize = 12;
 const MAX_SEASONAL_PACK_ID_CHARS: usize = 64;
 const MIN_TYPING_THRESHOLD_CPM: u32 = 80;
 const MAX_TYPING_THRESHOLD_CPM: u32 = 420;
+const MAX_TASK_AUTO_ARCHIVE_DAYS: u32 = 365;
+const MAX_TASK_TRASH_RETENTION_DAYS: u32 = 365;
+const MAX_ADAPTIVE_GOAL_STRETCH_PERCENT: u32 = 50;
+const MAX_GOAL_REWARD_COINS: u32 = 100;
+const MAX_PERFECT_DAY_BONUS_COINS: u32 = 500;
+const MAX_PERFECT_DAY_AFFECTION_BOOST: u32 = 50;
 
 fn is_allowed(value: &str, allowed: &[&str]) -> bool {
     allowed.iter().any(|candidate| *candidate == value)
@@ -97,6 +103,9 @@ pub(crate) fn sanitize_settings(settings: &mut Settings) {
     if !is_allowed(&settings.animation_budget, ALLOWED_ANIMATION_BUDGETS) {
         settings.animation_budget = "medium".to_string();
     }
+    if !is_allowed(&settings.xp_curve, crate::leveling::ALLOWED_XP_CURVES) {
+        settings.xp_curve = "quadratic".to_string();
+    }
     settings.sound_volume = if settings.sound_volume.is_finite() {
         settings.sound_volume.clamp(0.0, 1.0)
     } else {
@@ -117,6 +126,27 @@ pub(crate) fn sanitize_settings(settings: &mut Settings) {
     }
     settings.focus_allowlist = normalize_host_list(std::mem::take(&mut settings.focus_allowlist));
     settings.focus_blocklist = normalize_host_list(std::mem::take(&mut settings.focus_blocklist));
+    settings.task_auto_archive_days = settings
+        .task_auto_archive_days
+        .min(MAX_TASK_AUTO_ARCHIVE_DAYS);
+    settings.task_trash_retention_days = settings
+        .task_trash_retention_days
+        .min(MAX_TASK_TRASH_RETENTION_DAYS);
+    settings.adaptive_goal_stretch_percent = settings
+        .adaptive_goal_stretch_percent
+        .min(MAX_ADAPTIVE_GOAL_STRETCH_PERCENT);
+    settings.week_start_day = settings.week_start_day.min(6);
+    settings.goal_reward_coins = settings.goal_reward_coins.min(MAX_GOAL_REWARD_COINS);
+    settings.perfect_day_bonus_coins = settings
+        .perfect_day_bonus_coins
+        .min(MAX_PERFECT_DAY_BONUS_COINS);
+    settings.perfect_day_affection_boost = settings
+        .perfect_day_affection_boost
+        .min(MAX_PERFECT_DAY_AFFECTION_BOOST);
+    settings.time_zone = crate::clock::normalize_time_zone(&settings.time_zone);
+    settings.day_start_hour = settings
+        .day_start_hour
+        .min(crate::clock::MAX_DAY_START_HOUR);
 }
 
 fn normalize_notification_whitelist(input: Vec<String>) -> Vec<String> {
@@ -193,6 +223,11 @@ fn sanitize_patch(mut patch: SettingsPatch) -> Result<SettingsPatch, String> {
             return Err(format!("Invalid pet scene: {}", scene));
         }
     }
+    if let Some(curve) = patch.xp_curve.as_deref() {
+        if !is_allowed(curve, crate::leveling::ALLOWED_XP_CURVES) {
+            return Err(format!("Invalid XP curve: {}", curve));
+        }
+    }
     if let Some(volume) = patch.sound_volume {
         patch.sound_volume = Some(if volume.is_finite() {
             volume.clamp(0.0, 1.0)
@@ -228,10 +263,15 @@ fn sanitize_patch(mut patch: SettingsPatch) -> Result<SettingsPatch, String> {
 
 fn load_settings(app: &AppHandle) -> Result<Settings, String> {
     let store = app.store("store.json").map_err(|e| e.to_string())?;
-    let mut settings = store
-        .get("settings")
-        .and_then(|v| serde_json::from_value(v).ok())
-        .unwrap_or_default();
+    let mut settings = match store.get("settings") {
+        Some(value) => serde_json::from_value(value).unwrap_or_default(),
+        // Progress without saved settings predates the XP curve setting.
+        None if store.has("user_progress") => Settings {
+            xp_curve: crate::models::LEGACY_XP_CURVE.to_string(),
+            ..Default::default()
+        },
+        None => Settings::default(),
+    };
     sanitize_settings(&mut settings);
     Ok(settings)
 }
@@ -252,6 +292,12 @@ pub fn update_settings(
     let patch = sanitize_patch(patch)?;
     let patch_copy = patch.clone();
     let mut settings = load_settings(&app)?;
+    if let Some(skin) = patch.pet_skin.as_deref() {
+        crate::leveling::ensure_cosmetic_unlocked(&app, "skin", skin, &settings.pet_skin)?;
+    }
+    if let Some(scene) = patch.pet_scene.as_deref() {
+        crate::leveling::ensure_cosmetic_unlocked(&app, "scene", scene, &settings.pet_scene)?;
+    }
     patch.apply_to(&mut settings);
     sanitize_settings(&mut settings);
 
@@ -1,56 +1,507 @@
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use tauri::{AppHandle, Emitter};
 use tauri_plugin_store::StoreExt;
 
-use crate::events::{EVENT_COINS_CHANGED, EVENT_PET_STATE_CHANGED};
-use crate::models::{CoinBalance, PetState};
+use crate::events::EVENT_PET_STATE_CHANGED;
+use crate::models::{PetState, UserProgress};
+
+const BUNDLED_CATALOG: &str = include_str!("../../catalog/shop_catalog.json");
+const CATEGORY_ACCESSORY: &str = "accessory";
+const CATEGORY_FOOD: &str = "food";
+const ALLOWED_CATEGORIES: &[&str] = &[CATEGORY_ACCESSORY, CATEGORY_FOOD];
+/// Where accessories are worn, in the order `PetState.accessories` lists them.
+const ACCESSORY_SLOTS: &[&str] = &["head", "face", "neck", "held"];
+/// Units of a single food item the inventory holds.
+const MAX_FOOD_STACK: u32 = 99;
+/// Bumped whenever `normalize_pet_items` gains a one-time migration.
+const PET_ITEMS_VERSION: u32 = 1;
+const ALLOWED_RARITIES: &[&str] = &["common", "uncommon", "rare", "epic", "legendary"];
+/// Regular catalog items offered each day; seasonal stock comes on top.
+const DAILY_STOCK_SIZE: usize = 4;
+const DAILY_SALE_PERCENT: u32 = 25;
 
-#[derive(serde::Serialize)]
+static CATALOG: OnceLock<Result<ShopCatalog, String>> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ShopItem {
     pub id: String,
     pub name: String,
     pub cost: u32,
+    pub category: String,
+    pub rarity: String,
+    /// Slot an accessory occupies; food has none.
+    #[serde(default)]
+    pub slot: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub required_stage: u32,
+    #[serde(default)]
+    pub required_level: u32,
+    /// Species that may buy the item; empty means every species.
+    #[serde(default)]
+    pub species: Vec<String>,
+    /// First and last day (`YYYY-MM-DD`, inclusive) the item is on sale.
+    #[serde(default)]
+    pub available_from: Option<String>,
+    #[serde(default)]
+    pub available_until: Option<String>,
+    /// Set for food only: applied on top of the regular feed interaction.
+    #[serde(default)]
+    pub effects: Option<FoodEffects>,
 }
 
-fn shop_catalog() -> Vec<ShopItem> {
-    vec![
-        ShopItem {
-            id: "party_hat".into(),
-            name: "Party Hat".into(),
-            cost: 30,
-        },
-        ShopItem {
-            id: "bow_tie".into(),
-            name: "Bow Tie".into(),
-            cost: 20,
-        },
-        ShopItem {
-            id: "sunglasses".into(),
-            name: "Sunglasses".into(),
-            cost: 25,
-        },
-        ShopItem {
-            id: "scarf".into(),
-            name: "Scarf".into(),
-            cost: 35,
-        },
-        ShopItem {
-            id: "apple".into(),
-            name: "Apple".into(),
-            cost: 5,
-        },
-        ShopItem {
-            id: "cookie".into(),
-            name: "Cookie".into(),
-            cost: 10,
-        },
-    ]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+#[serde(rename_all = "camelCase")]
+pub struct FoodEffects {
+    pub hunger: i32,
+    pub affection: i32,
+    pub energy: i32,
+}
+
+/// Themed stock sold while its pack is enabled and one of its months is current.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeasonalShopPack {
+    pub id: String,
+    pub active_months: Vec<u32>,
+    pub items: Vec<ShopItem>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShopCatalog {
+    items: Vec<ShopItem>,
+    #[serde(default)]
+    seasonal_packs: Vec<SeasonalShopPack>,
+}
+
+impl ShopCatalog {
+    fn all_items(&self) -> impl Iterator<Item = &ShopItem> {
+        self.items.iter().chain(
+            self.seasonal_packs
+                .iter()
+                .flat_map(|pack| pack.items.iter()),
+        )
+    }
+}
+
+/// A catalog item in today's stock. `sale_cost` is set for the daily deal and only
+/// holds on `stocked_on`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShopStockItem {
+    #[serde(flatten)]
+    pub item: ShopItem,
+    pub sale_cost: Option<u32>,
+    pub seasonal_pack: Option<String>,
+    pub stocked_on: String,
+}
+
+impl ShopStockItem {
+    pub fn price(&self) -> u32 {
+        self.sale_cost.unwrap_or(self.item.cost)
+    }
+}
+
+fn validate_catalog(catalog: &ShopCatalog) -> Result<(), String> {
+    if catalog.items.is_empty() {
+        return Err("Shop catalog is empty".to_string());
+    }
+    let mut pack_ids = HashSet::new();
+    for pack in &catalog.seasonal_packs {
+        if pack.id.trim().is_empty() || !pack_ids.insert(pack.id.as_str()) {
+            return Err(format!(
+                "Duplicate or empty seasonal pack id: '{}'",
+                pack.id
+            ));
+        }
+        if pack.active_months.is_empty()
+            || pack
+                .active_months
+                .iter()
+                .any(|month| !(1..=12).contains(month))
+        {
+            return Err(format!("Seasonal pack {} has invalid months", pack.id));
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for item in catalog.all_items() {
+        if item.id.trim().is_empty() || !seen.insert(item.id.as_str()) {
+            return Err(format!("Duplicate or empty shop item id: '{}'", item.id));
+        }
+        if item.name.trim().is_empty() {
+            return Err(format!("Shop item {} has no name", item.id));
+        }
+        if item.cost == 0 {
+            return Err(format!("Shop item {} must cost something", item.id));
+        }
+        if !ALLOWED_CATEGORIES.contains(&item.category.as_str()) {
+            return Err(format!(
+                "Shop item {} has invalid category: {}",
+                item.id, item.category
+            ));
+        }
+        if !ALLOWED_RARITIES.contains(&item.rarity.as_str()) {
+            return Err(format!(
+                "Shop item {} has invalid rarity: {}",
+                item.id, item.rarity
+            ));
+        }
+        if item.species.iter().any(|species| species.trim().is_empty()) {
+            return Err(format!("Shop item {} has an empty species", item.id));
+        }
+        match item.slot.as_deref() {
+            Some(slot) if item.category != CATEGORY_ACCESSORY => {
+                return Err(format!(
+                    "Only accessories have a slot: {} ({})",
+                    item.id, slot
+                ));
+            }
+            Some(slot) if !ACCESSORY_SLOTS.contains(&slot) => {
+                return Err(format!("Shop item {} has invalid slot: {}", item.id, slot));
+            }
+            None if item.category == CATEGORY_ACCESSORY => {
+                return Err(format!("Accessory {} has no slot", item.id));
+            }
+            _ => {}
+        }
+        match item.effects {
+            None if item.category == CATEGORY_FOOD => {
+                return Err(format!("Food item {} has no effects", item.id));
+            }
+            Some(_) if item.category != CATEGORY_FOOD => {
+                return Err(format!("Only food items can have effects: {}", item.id));
+            }
+            Some(effects)
+                if [effects.hunger, effects.affection, effects.energy]
+                    .iter()
+                    .any(|delta| !(-100..=100).contains(delta)) =>
+            {
+                return Err(format!("Food item {} has out-of-range effects", item.id));
+            }
+            _ => {}
+        }
+        let from = parse_window_day(&item.id, item.available_from.as_deref())?;
+        let until = parse_window_day(&item.id, item.available_until.as_deref())?;
+        if let (Some(from), Some(until)) = (from, until) {
+            if from > until {
+                return Err(format!("Shop item {} ends before it starts", item.id));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_window_day(item_id: &str, day: Option<&str>) -> Result<Option<NaiveDate>, String> {
+    day.map(|day| {
+        NaiveDate::parse_from_str(day, "%Y-%m-%d")
+            .map_err(|_| format!("Shop item {} has invalid date: {}", item_id, day))
+    })
+    .transpose()
+}
+
+fn parse_catalog(json: &str) -> Result<ShopCatalog, String> {
+    let catalog: ShopCatalog =
+        serde_json::from_str(json).map_err(|e| format!("Invalid shop catalog: {}", e))?;
+    validate_catalog(&catalog)?;
+    Ok(catalog)
+}
+
+fn loaded_catalog() -> &'static Result<ShopCatalog, String> {
+    CATALOG.get_or_init(|| parse_catalog(BUNDLED_CATALOG))
+}
+
+fn catalog() -> &'static ShopCatalog {
+    static EMPTY: ShopCatalog = ShopCatalog {
+        items: Vec::new(),
+        seasonal_packs: Vec::new(),
+    };
+    loaded_catalog().as_ref().unwrap_or(&EMPTY)
+}
+
+/// Checked at startup so a broken bundled catalog stops the app instead of emptying the shop.
+/// Returns the number of items, seasonal ones included.
+pub fn validate_bundled_catalog() -> Result<usize, String> {
+    loaded_catalog()
+        .as_ref()
+        .map(|catalog| catalog.all_items().count())
+        .map_err(Clone::clone)
+}
+
+fn catalog_item(item_id: &str) -> Option<&'static ShopItem> {
+    catalog().all_items().find(|item| item.id == item_id)
+}
+
+/// Whether `item_id` names a regular or seasonal item of the shop catalog.
+pub(crate) fn is_catalog_item(item_id: &str) -> bool {
+    catalog_item(item_id).is_some()
+}
+
+/// Whether `item_id` is a catalog item the pet can wear.
+pub(crate) fn is_accessory_item(item_id: &str) -> bool {
+    catalog_item(item_id).is_some_and(|item| item.category == CATEGORY_ACCESSORY)
+}
+
+pub(crate) fn accessory_slot(item_id: &str) -> Option<&'static str> {
+    catalog_item(item_id)
+        .filter(|item| item.category == CATEGORY_ACCESSORY)
+        .and_then(|item| item.slot.as_deref())
+}
+
+/// Effects of feeding `item_id`, or `None` when it is not a food item.
+pub(crate) fn food_effects(item_id: &str) -> Option<FoodEffects> {
+    catalog_item(item_id)
+        .filter(|item| item.category == CATEGORY_FOOD)
+        .and_then(|item| item.effects)
+}
+
+/// Uses one unit of `item_id`, dropping the entry once it runs out.
+pub(crate) fn take_from_inventory(pet: &mut PetState, item_id: &str) -> bool {
+    match pet.inventory.get_mut(item_id) {
+        Some(units) if *units > 0 => {
+            *units -= 1;
+            if *units == 0 {
+                pet.inventory.remove(item_id);
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Fills slots from a flat accessory list, keeping the first accessory listed for a slot.
+pub(crate) fn slots_from_list(
+    accessories: &[String],
+) -> std::collections::BTreeMap<String, String> {
+    let mut slots = std::collections::BTreeMap::new();
+    for item_id in accessories {
+        if let Some(slot) = accessory_slot(item_id) {
+            slots
+                .entry(slot.to_string())
+                .or_insert_with(|| item_id.clone());
+        }
+    }
+    slots
+}
+
+/// Replaces what the pet wears with `equipped`, dropping entries the pet doesn't own or
+/// that sit in the wrong slot, and refreshes the worn list.
+pub(crate) fn set_equipped(
+    pet: &mut PetState,
+    mut equipped: std::collections::BTreeMap<String, String>,
+) {
+    equipped.retain(|slot, item_id| {
+        pet.owned_accessories.contains(item_id) && accessory_slot(item_id) == Some(slot.as_str())
+    });
+    pet.accessories = ACCESSORY_SLOTS
+        .iter()
+        .filter_map(|slot| equipped.get(*slot).cloned())
+        .collect();
+    pet.equipped = equipped;
+}
+
+/// Puts an owned accessory in its slot and returns the accessory it replaced.
+pub(crate) fn equip_item(pet: &mut PetState, item_id: &str) -> Result<Option<String>, String> {
+    if !pet.owned_accessories.iter().any(|owned| owned == item_id) {
+        return Err(format!("Accessory not owned: {}", item_id));
+    }
+    let slot = accessory_slot(item_id).ok_or_else(|| format!("Not an accessory: {}", item_id))?;
+    let mut equipped = pet.equipped.clone();
+    let replaced = equipped
+        .insert(slot.to_string(), item_id.to_string())
+        .filter(|previous| previous != item_id);
+    set_equipped(pet, equipped);
+    Ok(replaced)
 }
 
+/// Takes `item_id` off the pet; `false` when it wasn't being worn.
+pub(crate) fn unequip_item(pet: &mut PetState, item_id: &str) -> bool {
+    let mut equipped = pet.equipped.clone();
+    equipped.retain(|_, worn| worn != item_id);
+    let removed = equipped.len() != pet.equipped.len();
+    set_equipped(pet, equipped);
+    removed
+}
+
+/// Adds an item the pet was given: food as one more unit of inventory, an accessory
+/// as owned and worn.
+pub(crate) fn give_item(pet: &mut PetState, item_id: &str) {
+    if food_effects(item_id).is_some() {
+        let units = pet.inventory.entry(item_id.to_string()).or_insert(0);
+        *units = (*units + 1).min(MAX_FOOD_STACK);
+    } else if is_accessory_item(item_id) {
+        if !pet.owned_accessories.iter().any(|owned| owned == item_id) {
+            pet.owned_accessories.push(item_id.to_string());
+        }
+        // Every catalog accessory has a slot, so equipping it cannot fail here.
+        let _ = equip_item(pet, item_id);
+    }
+}
+
+/// Brings pets saved by older versions in line with the item model.
+///
+/// Once per pet, food kept as an accessory becomes one unit of inventory, pets that
+/// predate ownership own whatever they wear, and pets that predate slots have their
+/// worn accessories slotted. Worn accessories are then always limited to owned ones.
+pub(crate) fn normalize_pet_items(pet: &mut PetState) {
+    let migrate = pet.items_version < PET_ITEMS_VERSION;
+    if migrate {
+        let (food, accessories): (Vec<String>, Vec<String>) = std::mem::take(&mut pet.accessories)
+            .into_iter()
+            .partition(|id| food_effects(id).is_some());
+        pet.accessories = accessories;
+        for item_id in food {
+            let units = pet.inventory.entry(item_id).or_insert(0);
+            *units = (*units + 1).min(MAX_FOOD_STACK);
+        }
+        if pet.owned_accessories.is_empty() {
+            pet.owned_accessories = pet.accessories.clone();
+        }
+        pet.items_version = PET_ITEMS_VERSION;
+    }
+    let mut owned = Vec::new();
+    for item_id in std::mem::take(&mut pet.owned_accessories) {
+        if is_accessory_item(&item_id) && !owned.contains(&item_id) {
+            owned.push(item_id);
+        }
+    }
+    pet.owned_accessories = owned;
+
+    let equipped = if migrate && pet.equipped.is_empty() {
+        slots_from_list(&pet.accessories)
+    } else {
+        std::mem::take(&mut pet.equipped)
+    };
+    set_equipped(pet, equipped);
+}
+
+/// SplitMix64 step, so the daily rotation is the same on every machine.
+fn next_random(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+fn sale_price(cost: u32) -> u32 {
+    (cost * (100 - DAILY_SALE_PERCENT) / 100).max(1)
+}
+
+/// Picks today's regular stock and deal from `day` alone, then adds in-season items of
+/// enabled seasonal packs at full price.
+fn build_stock(
+    catalog: &ShopCatalog,
+    enabled_packs: &[String],
+    day: NaiveDate,
+) -> Vec<ShopStockItem> {
+    let stocked_on = day.format("%Y-%m-%d").to_string();
+    let mut state = u64::from(day.num_days_from_ce().unsigned_abs());
+    let mut candidates: Vec<&ShopItem> = catalog
+        .items
+        .iter()
+        .filter(|item| is_on_sale(item, day))
+        .collect();
+    for i in (1..candidates.len()).rev() {
+        let j = (next_random(&mut state) % (i as u64 + 1)) as usize;
+        candidates.swap(i, j);
+    }
+    candidates.truncate(DAILY_STOCK_SIZE);
+    let deal = if candidates.is_empty() {
+        None
+    } else {
+        Some(
+            candidates[(next_random(&mut state) % candidates.len() as u64) as usize]
+                .id
+                .clone(),
+        )
+    };
+    candidates.sort_by_key(|item| catalog.items.iter().position(|other| other.id == item.id));
+
+    let mut stock: Vec<ShopStockItem> = candidates
+        .into_iter()
+        .map(|item| ShopStockItem {
+            item: item.clone(),
+            sale_cost: (deal.as_deref() == Some(item.id.as_str())).then(|| sale_price(item.cost)),
+            seasonal_pack: None,
+            stocked_on: stocked_on.clone(),
+        })
+        .collect();
+
+    for pack in catalog.seasonal_packs.iter().filter(|pack| {
+        enabled_packs.contains(&pack.id) && pack.active_months.contains(&day.month())
+    }) {
+        stock.extend(
+            pack.items
+                .iter()
+                .filter(|item| is_on_sale(item, day))
+                .map(|item| ShopStockItem {
+                    item: item.clone(),
+                    sale_cost: None,
+                    seasonal_pack: Some(pack.id.clone()),
+                    stocked_on: stocked_on.clone(),
+                }),
+        );
+    }
+    stock
+}
+
+fn todays_stock(app: &AppHandle) -> Result<Vec<ShopStockItem>, String> {
+    let settings = crate::commands::settings::get_settings(app.clone())?;
+    Ok(build_stock(
+        catalog(),
+        &settings.enabled_seasonal_packs,
+        crate::clock::today_date(app),
+    ))
+}
+
+fn is_on_sale(item: &ShopItem, today: NaiveDate) -> bool {
+    let from = parse_window_day(&item.id, item.available_from.as_deref()).unwrap_or(None);
+    let until = parse_window_day(&item.id, item.available_until.as_deref()).unwrap_or(None);
+    from.map_or(true, |from| today >= from) && until.map_or(true, |until| today <= until)
+}
+
+/// Explains why `pet` cannot buy `item` today, if it cannot.
+fn check_purchasable(
+    item: &ShopItem,
+    pet: &PetState,
+    level: u32,
+    today: NaiveDate,
+) -> Result<(), String> {
+    if !is_on_sale(item, today) {
+        return Err(format!("{} is not on sale right now", item.name));
+    }
+    if !item.species.is_empty() && !item.species.contains(&pet.species_id) {
+        return Err(format!("{} is not available for this species", item.name));
+    }
+    if pet.current_stage < item.required_stage {
+        return Err(format!(
+            "{} requires pet stage {}",
+            item.name, item.required_stage
+        ));
+    }
+    if level < item.required_level {
+        return Err(format!(
+            "{} requires level {}",
+            item.name, item.required_level
+        ));
+    }
+    Ok(())
+}
+
+/// Returns today's stock with sale prices; stage, level and species locks are left to the caller.
 #[tauri::command]
-pub fn get_shop_items() -> Vec<ShopItem> {
-    shop_catalog()
+pub fn get_shop_items(app: AppHandle) -> Result<Vec<ShopStockItem>, String> {
+    todays_stock(&app)
 }
 
 #[tauri::command]
@@ -66,31 +517,84 @@ pub fn purchase_item(
         .get("pet")
         .and_then(|v| serde_json::from_value(v).ok())
         .unwrap_or_default();
+    normalize_pet_items(&mut pet);
 
-    if pet.accessories.contains(&item_id) {
+    if !is_catalog_item(&item_id) {
+        return Err("Item not found".to_string());
+    }
+    // The deal price is only honoured when the stock is built for the current day.
+    let offer = todays_stock(&app)?
+        .into_iter()
+        .find(|offer| offer.item.id == item_id)
+        .ok_or_else(|| "Item is not in stock today".to_string())?;
+    let item = &offer.item;
+    let is_food = item.category == CATEGORY_FOOD;
+    if is_food {
+        if pet.inventory.get(&item_id).copied().unwrap_or(0) >= MAX_FOOD_STACK {
+            return Err(format!(
+                "Cannot carry more than {} {}",
+                MAX_FOOD_STACK, item.name
+            ));
+        }
+    } else if pet.owned_accessories.contains(&item_id) {
         return Err("Already owned".to_string());
     }
+    let progress: UserProgress = store
+        .get("user_progress")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    check_purchasable(item, &pet, progress.level, crate::clock::today_date(&app))?;
 
-    let catalog = shop_catalog();
-    let item = catalog
-        .iter()
-        .find(|i| i.id == item_id)
-        .ok_or_else(|| "Item not found".to_string())?;
+    crate::commands::coins::debit_coins(
+        &app,
+        offer.price(),
+        crate::commands::coins::COIN_SOURCE_PURCHASE,
+        Some(&item.id),
+    )?;
 
-    let mut coins: CoinBalance = store
-        .get("coins")
+    // A new accessory goes on right away, as it did before equipping was explicit.
+    give_item(&mut pet, &item_id);
+    store.set("pet", json!(pet));
+    let _ = app.emit(EVENT_PET_STATE_CHANGED, &pet);
+
+    Ok(pet)
+}
+
+/// Returns a recent shop purchase: the item (or one unit of food) leaves the pet and its
+/// coins come back.
+#[tauri::command]
+pub fn refund_purchase(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    transaction_id: String,
+) -> Result<PetState, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+
+    let mut pet: PetState = store
+        .get("pet")
         .and_then(|v| serde_json::from_value(v).ok())
         .unwrap_or_default();
+    normalize_pet_items(&mut pet);
 
-    if coins.available() < item.cost {
-        return Err("Insufficient coins".to_string());
-    }
-
-    coins.spent += item.cost;
-    store.set("coins", json!(coins));
-    let _ = app.emit(EVENT_COINS_CHANGED, &coins);
+    crate::commands::coins::refund_purchase_with(&app, &transaction_id, |purchase| {
+        let item_id = purchase.reference_id.as_deref().unwrap_or_default();
+        if food_effects(item_id).is_some() {
+            return if take_from_inventory(&mut pet, item_id) {
+                Ok(())
+            } else {
+                Err("Item is no longer owned".to_string())
+            };
+        }
+        let before = pet.owned_accessories.len();
+        pet.owned_accessories.retain(|owned| owned != item_id);
+        if pet.owned_accessories.len() == before {
+            return Err("Item is no longer owned".to_string());
+        }
+        unequip_item(&mut pet, item_id);
+        Ok(())
+    })?;
 
-    pet.accessories.push(item_id);
     store.set("pet", json!(pet));
     let _ = app.emit(EVENT_PET_STATE_CHANGED, &pet);
 
@@ -101,14 +605,42 @@ pub fn purchase_item(
 mod tests {
     use super::*;
 
-    #[test]
-    fn catalog_has_6_items() {
-        assert_eq!(shop_catalog().len(), 6);
+    fn catalog_of(items: Vec<ShopItem>) -> ShopCatalog {
+        ShopCatalog {
+            items,
+            seasonal_packs: vec![],
+        }
+    }
+
+    fn pack(id: &str, active_months: Vec<u32>, items: Vec<ShopItem>) -> SeasonalShopPack {
+        SeasonalShopPack {
+            id: id.to_string(),
+            active_months,
+            items,
+        }
+    }
+
+    fn item(id: &str) -> ShopItem {
+        ShopItem {
+            id: id.to_string(),
+            name: "Test Item".to_string(),
+            cost: 10,
+            category: "accessory".to_string(),
+            rarity: "common".to_string(),
+            slot: Some("head".to_string()),
+            description: String::new(),
+            required_stage: 0,
+            required_level: 0,
+            species: vec![],
+            available_from: None,
+            available_until: None,
+            effects: None,
+        }
     }
 
     #[test]
     fn catalog_no_duplicate_ids() {
-        let catalog = shop_catalog();
+        let catalog = &catalog().items;
         let ids: Vec<_> = catalog.iter().map(|i| &i.id).collect();
         let unique: std::collections::HashSet<_> = ids.iter().collect();
         assert_eq!(ids.len(), unique.len());
@@ -116,21 +648,21 @@ mod tests {
 
     #[test]
     fn catalog_all_positive_costs() {
-        for item in shop_catalog() {
+        for item in &catalog().items {
             assert!(item.cost > 0, "item {} should have positive cost", item.id);
         }
     }
 
     #[test]
     fn catalog_all_have_names() {
-        for item in shop_catalog() {
+        for item in &catalog().items {
             assert!(!item.name.is_empty(), "item {} should have a name", item.id);
         }
     }
 
     #[test]
     fn catalog_contains_expected_items() {
-        let catalog = shop_catalog();
+        let catalog = &catalog().items;
         let ids: Vec<_> = catalog.iter().map(|i| i.id.as_str()).collect();
         assert!(ids.contains(&"party_hat"));
         assert!(ids.contains(&"bow_tie"));
@@ -142,7 +674,7 @@ mod tests {
 
     #[test]
     fn catalog_item_costs() {
-        let catalog = shop_catalog();
+        let catalog = &catalog().items;
         let find = |id: &str| catalog.iter().find(|i| i.id == id).unwrap();
         assert_eq!(find("party_hat").cost, 30);
         assert_eq!(find("bow_tie").cost, 20);
@@ -154,20 +686,334 @@ mod tests {
 
     #[test]
     fn shop_item_serializes_camel_case() {
-        let item = ShopItem {
-            id: "test".to_string(),
-            name: "Test Item".to_string(),
-            cost: 10,
-        };
+        let item = item("test");
         let json = serde_json::to_value(&item).unwrap();
         assert!(json.get("id").is_some());
         assert!(json.get("name").is_some());
         assert!(json.get("cost").is_some());
+        assert!(json.get("requiredStage").is_some());
+        assert!(json.get("availableFrom").is_some());
+    }
+
+    #[test]
+    fn bundled_catalog_is_valid() {
+        // Six regular items plus three seasonal ones.
+        assert_eq!(catalog().items.len(), 6);
+        assert_eq!(validate_bundled_catalog(), Ok(9));
+    }
+
+    #[test]
+    fn catalog_drives_customization_items() {
+        assert!(is_catalog_item("scarf"));
+        assert!(!is_catalog_item("jetpack"));
+    }
+
+    #[test]
+    fn validate_catalog_rejects_bad_entries() {
+        assert!(validate_catalog(&catalog_of(vec![item("a"), item("b")])).is_ok());
+        assert!(validate_catalog(&catalog_of(vec![])).is_err());
+        assert!(validate_catalog(&catalog_of(vec![item("a"), item("a")])).is_err());
+
+        let mut rarity = item("a");
+        rarity.rarity = "mythic".to_string();
+        assert!(validate_catalog(&catalog_of(vec![rarity])).is_err());
+
+        let mut window = item("a");
+        window.available_from = Some("2026-12-31".to_string());
+        window.available_until = Some("2026-12-01".to_string());
+        assert!(validate_catalog(&catalog_of(vec![window])).is_err());
+
+        let mut bad_date = item("a");
+        bad_date.available_until = Some("soon".to_string());
+        assert!(validate_catalog(&catalog_of(vec![bad_date])).is_err());
+
+        let mut clashing_pack = catalog_of(vec![item("a")]);
+        clashing_pack
+            .seasonal_packs
+            .push(pack("spring", vec![3], vec![item("a")]));
+        assert!(validate_catalog(&clashing_pack).is_err());
+
+        let mut bad_months = catalog_of(vec![item("a")]);
+        bad_months
+            .seasonal_packs
+            .push(pack("spring", vec![13], vec![item("b")]));
+        assert!(validate_catalog(&bad_months).is_err());
+
+        assert!(parse_catalog("{").is_err());
+    }
+
+    #[test]
+    fn daily_stock_is_deterministic_with_one_deal() {
+        let catalog = catalog_of((0..8).map(|i| item(&format!("item{}", i))).collect());
+        let day = NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        let stock = build_stock(&catalog, &[], day);
+        let ids = |stock: &[ShopStockItem]| -> Vec<String> {
+            stock.iter().map(|offer| offer.item.id.clone()).collect()
+        };
+
+        assert_eq!(stock.len(), DAILY_STOCK_SIZE);
+        assert_eq!(ids(&stock), ids(&build_stock(&catalog, &[], day)));
+        let deals: Vec<&ShopStockItem> = stock
+            .iter()
+            .filter(|offer| offer.sale_cost.is_some())
+            .collect();
+        assert_eq!(deals.len(), 1);
+        assert_eq!(deals[0].price(), sale_price(deals[0].item.cost));
+        assert!(deals[0].price() < deals[0].item.cost);
+        assert!(stock.iter().all(|offer| offer.stocked_on == "2026-03-14"));
+
+        let rotated = (1..=7).any(|offset| {
+            let other = day + chrono::Duration::days(offset);
+            ids(&build_stock(&catalog, &[], other)) != ids(&stock)
+        });
+        assert!(rotated);
+    }
+
+    #[test]
+    fn seasonal_stock_needs_enabled_pack_and_active_month() {
+        let mut catalog = catalog_of(vec![item("a")]);
+        catalog
+            .seasonal_packs
+            .push(pack("spring", vec![3, 4], vec![item("flower")]));
+        let march = NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        let june = NaiveDate::from_ymd_opt(2026, 6, 14).unwrap();
+        let enabled = vec!["spring".to_string()];
+        let has_flower = |stock: Vec<ShopStockItem>| {
+            stock.iter().any(|offer| {
+                offer.item.id == "flower" && offer.seasonal_pack.as_deref() == Some("spring")
+            })
+        };
+
+        assert!(has_flower(build_stock(&catalog, &enabled, march)));
+        assert!(!has_flower(build_stock(&catalog, &enabled, june)));
+        assert!(!has_flower(build_stock(&catalog, &[], march)));
+    }
+
+    #[test]
+    fn sale_price_discounts_but_never_reaches_zero() {
+        assert_eq!(sale_price(40), 30);
+        assert_eq!(sale_price(1), 1);
+    }
+
+    #[test]
+    fn check_purchasable_applies_window_species_stage_and_level() {
+        let today = NaiveDate::from_ymd_opt(2026, 12, 10).unwrap();
+        let pet = PetState::default();
+        let mut gated = item("winter_hat");
+        assert!(check_purchasable(&gated, &pet, 1, today).is_ok());
+
+        gated.available_from = Some("2026-12-01".to_string());
+        gated.available_until = Some("2026-12-31".to_string());
+        assert!(check_purchasable(&gated, &pet, 1, today).is_ok());
+        let january = NaiveDate::from_ymd_opt(2027, 1, 2).unwrap();
+        assert!(check_purchasable(&gated, &pet, 1, january).is_err());
+
+        gated.species = vec!["cat".to_string()];
+        assert!(check_purchasable(&gated, &pet, 1, today).is_err());
+        gated.species.push(pet.species_id.clone());
+        assert!(check_purchasable(&gated, &pet, 1, today).is_ok());
+
+        gated.required_stage = pet.current_stage + 1;
+        assert!(check_purchasable(&gated, &pet, 1, today).is_err());
+        gated.required_stage = 0;
+
+        gated.required_level = 5;
+        assert!(check_purchasable(&gated, &pet, 4, today).is_err());
+        assert!(check_purchasable(&gated, &pet, 5, today).is_ok());
+    }
+
+    #[test]
+    fn food_items_need_effects_and_accessories_must_not_have_them() {
+        let mut food = item("snack");
+        food.category = CATEGORY_FOOD.to_string();
+        food.slot = None;
+        assert!(validate_catalog(&catalog_of(vec![food.clone()])).is_err());
+
+        food.effects = Some(FoodEffects {
+            hunger: -10,
+            ..Default::default()
+        });
+        assert!(validate_catalog(&catalog_of(vec![food.clone()])).is_ok());
+
+        food.effects = Some(FoodEffects {
+            hunger: -150,
+            ..Default::default()
+        });
+        assert!(validate_catalog(&catalog_of(vec![food])).is_err());
+
+        let mut hat = item("hat");
+        hat.effects = Some(FoodEffects::default());
+        assert!(validate_catalog(&catalog_of(vec![hat])).is_err());
+    }
+
+    #[test]
+    fn legacy_food_accessories_move_into_inventory() {
+        let mut pet = PetState {
+            accessories: vec![
+                "apple".to_string(),
+                "party_hat".to_string(),
+                "cookie".to_string(),
+            ],
+            inventory: [("apple".to_string(), 2)].into_iter().collect(),
+            ..Default::default()
+        };
+
+        normalize_pet_items(&mut pet);
+
+        assert_eq!(pet.accessories, vec!["party_hat".to_string()]);
+        assert_eq!(pet.inventory.get("apple"), Some(&3));
+        assert_eq!(pet.inventory.get("cookie"), Some(&1));
+    }
+
+    #[test]
+    fn take_from_inventory_uses_one_unit_and_drops_empty_entries() {
+        let mut pet = PetState {
+            inventory: [("cookie".to_string(), 2)].into_iter().collect(),
+            ..Default::default()
+        };
+
+        assert!(take_from_inventory(&mut pet, "cookie"));
+        assert_eq!(pet.inventory.get("cookie"), Some(&1));
+        assert!(take_from_inventory(&mut pet, "cookie"));
+        assert!(!pet.inventory.contains_key("cookie"));
+        assert!(!take_from_inventory(&mut pet, "cookie"));
+        assert!(!take_from_inventory(&mut pet, "apple"));
+    }
+
+    #[test]
+    fn normalize_pet_items_limits_worn_accessories_to_owned() {
+        let mut legacy = PetState {
+            accessories: vec!["scarf".to_string(), "scarf".to_string()],
+            ..Default::default()
+        };
+        normalize_pet_items(&mut legacy);
+        assert_eq!(legacy.owned_accessories, vec!["scarf".to_string()]);
+        assert_eq!(legacy.accessories, vec!["scarf".to_string()]);
+
+        let mut pet = PetState {
+            accessories: vec!["scarf".to_string(), "party_hat".to_string()],
+            owned_accessories: vec!["party_hat".to_string(), "unknown".to_string()],
+            ..Default::default()
+        };
+        normalize_pet_items(&mut pet);
+        assert_eq!(pet.owned_accessories, vec!["party_hat".to_string()]);
+        assert_eq!(pet.accessories, vec!["party_hat".to_string()]);
+    }
+
+    #[test]
+    fn legacy_pet_migration_runs_once() {
+        let mut pet = PetState {
+            accessories: vec!["scarf".to_string()],
+            ..Default::default()
+        };
+        normalize_pet_items(&mut pet);
+        assert_eq!(pet.owned_accessories, vec!["scarf".to_string()]);
+        assert_eq!(pet.items_version, PET_ITEMS_VERSION);
+
+        // Worn items no longer imply ownership once the pet has been migrated.
+        pet.owned_accessories.clear();
+        pet.accessories = vec!["party_hat".to_string()];
+        normalize_pet_items(&mut pet);
+        assert!(pet.owned_accessories.is_empty());
+        assert!(pet.accessories.is_empty());
+    }
+
+    #[test]
+    fn given_items_are_owned_and_worn_or_stocked() {
+        let mut pet = PetState {
+            items_version: PET_ITEMS_VERSION,
+            ..Default::default()
+        };
+        give_item(&mut pet, "party_hat");
+        give_item(&mut pet, "party_hat");
+        give_item(&mut pet, "apple");
+
+        assert_eq!(pet.owned_accessories, vec!["party_hat".to_string()]);
+        assert_eq!(pet.accessories, vec!["party_hat".to_string()]);
+        assert_eq!(
+            pet.equipped.get("head").map(String::as_str),
+            Some("party_hat")
+        );
+        assert_eq!(pet.inventory.get("apple"), Some(&1));
+    }
+
+    #[test]
+    fn accessories_need_a_known_slot_and_food_has_none() {
+        let mut hat = item("hat");
+        hat.slot = None;
+        assert!(validate_catalog(&catalog_of(vec![hat.clone()])).is_err());
+        hat.slot = Some("tail".to_string());
+        assert!(validate_catalog(&catalog_of(vec![hat])).is_err());
+
+        let mut food = item("snack");
+        food.category = CATEGORY_FOOD.to_string();
+        food.effects = Some(FoodEffects::default());
+        assert!(validate_catalog(&catalog_of(vec![food])).is_err());
+    }
+
+    #[test]
+    fn equipping_replaces_the_slot_occupant() {
+        let mut pet = PetState {
+            owned_accessories: vec![
+                "party_hat".to_string(),
+                "flower_crown".to_string(),
+                "scarf".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(equip_item(&mut pet, "scarf"), Ok(None));
+        assert_eq!(equip_item(&mut pet, "party_hat"), Ok(None));
+        assert_eq!(
+            equip_item(&mut pet, "flower_crown"),
+            Ok(Some("party_hat".to_string()))
+        );
+        assert_eq!(
+            pet.accessories,
+            vec!["flower_crown".to_string(), "scarf".to_string()]
+        );
+        assert_eq!(
+            pet.equipped.get("head").map(String::as_str),
+            Some("flower_crown")
+        );
+
+        assert!(equip_item(&mut pet, "sunglasses").is_err());
+        assert!(unequip_item(&mut pet, "scarf"));
+        assert!(!unequip_item(&mut pet, "scarf"));
+        assert_eq!(pet.accessories, vec!["flower_crown".to_string()]);
+    }
+
+    #[test]
+    fn set_equipped_drops_unowned_and_misplaced_items() {
+        let mut pet = PetState {
+            owned_accessories: vec!["sunglasses".to_string(), "scarf".to_string()],
+            ..Default::default()
+        };
+        let equipped = [
+            ("head", "party_hat"),
+            ("face", "sunglasses"),
+            ("held", "scarf"),
+        ]
+        .into_iter()
+        .map(|(slot, item_id)| (slot.to_string(), item_id.to_string()))
+        .collect();
+
+        set_equipped(&mut pet, equipped);
+
+        assert_eq!(pet.equipped.len(), 1);
+        assert_eq!(pet.accessories, vec!["sunglasses".to_string()]);
     }
 
     #[test]
-    fn get_shop_items_returns_catalog() {
-        let items = get_shop_items();
-        assert_eq!(items.len(), 6);
+    fn slots_from_list_keeps_the_first_item_per_slot() {
+        let slots = slots_from_list(&[
+            "party_hat".to_string(),
+            "flower_crown".to_string(),
+            "apple".to_string(),
+            "bow_tie".to_string(),
+        ]);
+        assert_eq!(slots.get("head").map(String::as_str), Some("party_hat"));
+        assert_eq!(slots.get("neck").map(String::as_str), Some("bow_tie"));
+        assert_eq!(slots.len(), 2);
     }
 }
@@ -0,0 +1,576 @@
+use tauri::AppHandle;
+
+use crate::commands::tasks::{
+    active_task_count, load_task_lists, load_tasks, next_sort_index, normalize_due_date,
+    normalize_tag, normalize_task_list_name, normalize_task_title, save_task_lists, save_tasks,
+    sort_tasks, MAX_TASKS, MAX_TASK_LISTS,
+};
+use crate::models::{Task, TaskImportLine, TaskImportReport, TaskList, INBOX_TASK_LIST_ID};
+
+const MAX_IMPORT_CHARS: usize = 500_000;
+const ALLOWED_TRANSFER_FORMATS: &[&str] = &["todotxt", "markdown"];
+
+#[derive(Debug, Default, PartialEq)]
+struct ParsedTask {
+    title: String,
+    completed: bool,
+    priority: String,
+    due_date: Option<String>,
+    list_name: Option<String>,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum MarkdownLine {
+    Item(ParsedTask),
+    Heading(String),
+    Other,
+}
+
+fn normalize_transfer_format(format: &str) -> Result<String, String> {
+    let normalized = format
+        .trim()
+        .to_ascii_lowercase()
+        .replace(['.', '-', '_'], "");
+    if ALLOWED_TRANSFER_FORMATS.contains(&normalized.as_str()) {
+        Ok(normalized)
+    } else {
+        Err(format!("Unsupported task format: {}", format))
+    }
+}
+
+fn is_iso_date(token: &str) -> bool {
+    chrono::NaiveDate::parse_from_str(token, "%Y-%m-%d").is_ok()
+}
+
+/// todo.txt priorities A and B map to high and medium; anything lower is low.
+fn priority_from_letter(letter: char) -> &'static str {
+    match letter {
+        'A' => "high",
+        'B' => "medium",
+        _ => "low",
+    }
+}
+
+fn priority_letter(priority: &str) -> Option<char> {
+    match priority {
+        "high" => Some('A'),
+        "medium" => Some('B'),
+        "low" => Some('C'),
+        _ => None,
+    }
+}
+
+/// Parses one todo.txt line: `x`, `(A)`, leading dates, `+project`, `@context` and `due:`.
+fn parse_todo_txt_line(line: &str) -> Result<ParsedTask, String> {
+    let mut parsed = ParsedTask {
+        priority: "none".to_string(),
+        ..Default::default()
+    };
+    let mut tokens = line.split_whitespace().peekable();
+
+    if tokens.peek() == Some(&"x") {
+        parsed.completed = true;
+        tokens.next();
+    }
+    if let Some(token) = tokens.peek() {
+        let bytes = token.as_bytes();
+        if bytes.len() == 3 && bytes[0] == b'(' && bytes[2] == b')' && bytes[1].is_ascii_uppercase()
+        {
+            parsed.priority = priority_from_letter(bytes[1] as char).to_string();
+            tokens.next();
+        }
+    }
+    // Completion and creation dates.
+    for _ in 0..2 {
+        if tokens.peek().is_some_and(|token| is_iso_date(token)) {
+            tokens.next();
+        }
+    }
+
+    let mut words = Vec::new();
+    for token in tokens {
+        if let Some(project) = token.strip_prefix('+').filter(|p| !p.is_empty()) {
+            if parsed.list_name.is_none() {
+                parsed.list_name = Some(project.replace('_', " "));
+            } else if let Some(tag) = normalize_tag(project) {
+                parsed.tags.push(tag);
+            }
+        } else if let Some(context) = token.strip_prefix('@').filter(|c| !c.is_empty()) {
+            if let Some(tag) = normalize_tag(context) {
+                if !parsed.tags.contains(&tag) {
+                    parsed.tags.push(tag);
+                }
+            }
+        } else if let Some(due) = token.strip_prefix("due:") {
+            parsed.due_date = Some(normalize_due_date(due.to_string())?).filter(|d| !d.is_empty());
+        } else {
+            words.push(token);
+        }
+    }
+    parsed.title = words.join(" ");
+    Ok(parsed)
+}
+
+fn parse_markdown_line(line: &str) -> MarkdownLine {
+    let trimmed = line.trim();
+    if let Some(heading) = trimmed.strip_prefix('#') {
+        let name = heading.trim_start_matches('#').trim();
+        if !name.is_empty() {
+            return MarkdownLine::Heading(name.to_string());
+        }
+    }
+
+    let Some(rest) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|bullet| trimmed.strip_prefix(bullet))
+    else {
+        return MarkdownLine::Other;
+    };
+    let rest = rest.trim_start();
+    let (completed, title) = if let Some(title) = rest.strip_prefix("[ ]") {
+        (false, title)
+    } else if let Some(title) = rest
+        .strip_prefix("[x]")
+        .or_else(|| rest.strip_prefix("[X]"))
+    {
+        (true, title)
+    } else {
+        return MarkdownLine::Other;
+    };
+
+    MarkdownLine::Item(ParsedTask {
+        title: title.trim().to_string(),
+        completed,
+        priority: "none".to_string(),
+        ..Default::default()
+    })
+}
+
+fn list_names_match(a: &str, b: &str) -> bool {
+    a.replace('_', " ")
+        .eq_ignore_ascii_case(&b.replace('_', " "))
+}
+
+/// Where an imported task goes: a list that already exists, or one to create once the
+/// line has been accepted.
+#[derive(Debug, PartialEq)]
+enum ListTarget {
+    Existing(String),
+    New(String),
+}
+
+/// Finds the list with this name, planning a new one while there is room; falls back to
+/// the Inbox.
+fn plan_list(lists: &[TaskList], name: &str) -> ListTarget {
+    if let Some(list) = lists.iter().find(|list| list_names_match(&list.name, name)) {
+        return ListTarget::Existing(list.id.clone());
+    }
+    match normalize_task_list_name(name.to_string()) {
+        Ok(name) if lists.len() < MAX_TASK_LISTS => ListTarget::New(name),
+        _ => ListTarget::Existing(INBOX_TASK_LIST_ID.to_string()),
+    }
+}
+
+fn create_list(lists: &mut Vec<TaskList>, name: String, now: &str) -> String {
+    let list = TaskList {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        created_at: now.to_string(),
+    };
+    let id = list.id.clone();
+    lists.push(list);
+    id
+}
+
+fn report_line(
+    report: &mut TaskImportReport,
+    line: u32,
+    status: &str,
+    title: &str,
+    message: Option<String>,
+) {
+    match status {
+        "imported" => report.imported += 1,
+        "duplicate" => report.duplicates += 1,
+        "rejected" => report.rejected += 1,
+        _ => {}
+    }
+    report.lines.push(TaskImportLine {
+        line,
+        status: status.to_string(),
+        title: title.to_string(),
+        message,
+    });
+}
+
+fn import_parsed_task(
+    tasks: &mut Vec<Task>,
+    lists: &mut Vec<TaskList>,
+    parsed: ParsedTask,
+    default_list: Option<&str>,
+    now: &str,
+) -> Result<String, (String, String)> {
+    let title =
+        normalize_task_title(parsed.title.clone()).map_err(|err| ("rejected".to_string(), err))?;
+    let target = match parsed.list_name.as_deref().or(default_list) {
+        Some(name) => plan_list(lists, name),
+        None => ListTarget::Existing(INBOX_TASK_LIST_ID.to_string()),
+    };
+    // A list that doesn't exist yet can't hold a duplicate.
+    if let ListTarget::Existing(list_id) = &target {
+        if tasks.iter().any(|task| {
+            task.deleted_at.is_none()
+                && task.list_id == *list_id
+                && task.title.eq_ignore_ascii_case(&title)
+        }) {
+            return Err((
+                "duplicate".to_string(),
+                "A task with this title already exists".to_string(),
+            ));
+        }
+    }
+    if active_task_count(tasks) >= MAX_TASKS {
+        return Err((
+            "rejected".to_string(),
+            format!("Cannot have more than {} tasks", MAX_TASKS),
+        ));
+    }
+    let list_id = match target {
+        ListTarget::Existing(list_id) => list_id,
+        ListTarget::New(name) => create_list(lists, name, now),
+    };
+
+    tasks.push(Task {
+        id: uuid::Uuid::new_v4().to_string(),
+        title: title.clone(),
+        completed: parsed.completed,
+        created_at: now.to_string(),
+        priority: parsed.priority,
+        due_date: parsed.due_date,
+        tags: parsed.tags,
+        completed_at: parsed.completed.then(|| now.to_string()),
+        sort_index: next_sort_index(tasks, &list_id),
+        list_id,
+        ..Default::default()
+    });
+    Ok(title)
+}
+
+/// Imports every line of `content`, returning a report entry for each non-blank line.
+fn import_into(
+    tasks: &mut Vec<Task>,
+    lists: &mut Vec<TaskList>,
+    content: &str,
+    format: &str,
+    now: &str,
+) -> TaskImportReport {
+    let mut report = TaskImportReport::default();
+    let mut current_heading: Option<String> = None;
+
+    for (idx, raw) in content.lines().enumerate() {
+        let line = (idx + 1) as u32;
+        let text = raw.trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        let parsed = if format == "markdown" {
+            match parse_markdown_line(text) {
+                MarkdownLine::Item(parsed) => Ok(parsed),
+                MarkdownLine::Heading(name) => {
+                    report_line(
+                        &mut report,
+                        line,
+                        "skipped",
+                        &name,
+                        Some("List heading".to_string()),
+                    );
+                    current_heading = Some(name);
+                    continue;
+                }
+                MarkdownLine::Other => {
+                    report_line(
+                        &mut report,
+                        line,
+                        "skipped",
+                        text,
+                        Some("Not a checklist item".to_string()),
+                    );
+                    continue;
+                }
+            }
+        } else {
+            parse_todo_txt_line(text)
+        };
+
+        let outcome = parsed
+            .map_err(|err| ("rejected".to_string(), err))
+            .and_then(|parsed| {
+                import_parsed_task(tasks, lists, parsed, current_heading.as_deref(), now)
+            });
+        match outcome {
+            Ok(title) => report_line(&mut report, line, "imported", &title, None),
+            Err((status, message)) => report_line(&mut report, line, &status, text, Some(message)),
+        }
+    }
+
+    report
+}
+
+fn todo_txt_line(task: &Task, lists: &[TaskList]) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    if task.completed {
+        parts.push("x".to_string());
+        if let Some(date) = task.completed_at.as_deref().and_then(|at| at.get(..10)) {
+            parts.push(date.to_string());
+        }
+    } else if let Some(letter) = priority_letter(&task.priority) {
+        parts.push(format!("({})", letter));
+    }
+    parts.push(task.title.clone());
+    if task.list_id != INBOX_TASK_LIST_ID {
+        if let Some(list) = lists.iter().find(|list| list.id == task.list_id) {
+            parts.push(format!(
+                "+{}",
+                list.name.split_whitespace().collect::<Vec<_>>().join("_")
+            ));
+        }
+    }
+    for tag in &task.tags {
+        parts.push(format!("@{}", tag));
+    }
+    if let Some(due_date) = task.due_date.as_deref() {
+        parts.push(format!("due:{}", due_date));
+    }
+    parts.join(" ")
+}
+
+fn markdown_sections(tasks: &[Task], lists: &[TaskList]) -> String {
+    let mut sections = Vec::new();
+    for list in lists {
+        let items: Vec<String> = tasks
+            .iter()
+            .filter(|task| task.list_id == list.id)
+            .map(|task| {
+                format!(
+                    "- [{}] {}",
+                    if task.completed { "x" } else { " " },
+                    task.title
+                )
+            })
+            .collect();
+        if !items.is_empty() {
+            sections.push(format!("## {}\n\n{}\n", list.name, items.join("\n")));
+        }
+    }
+    sections.join("\n")
+}
+
+fn export_as(tasks: &[Task], lists: &[TaskList], format: &str) -> String {
+    if format == "markdown" {
+        return markdown_sections(tasks, lists);
+    }
+    let mut output: String = tasks
+        .iter()
+        .map(|task| todo_txt_line(task, lists))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    output
+}
+
+/// Imports todo.txt lines or Markdown `- [ ]` checklists. Markdown headings select the list.
+#[tauri::command]
+pub fn import_tasks(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    content: String,
+    format: String,
+) -> Result<TaskImportReport, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let format = normalize_transfer_format(&format)?;
+    if content.len() > MAX_IMPORT_CHARS {
+        return Err(format!("Import exceeds {} characters", MAX_IMPORT_CHARS));
+    }
+
+    let mut tasks = load_tasks(&app)?;
+    let mut lists = load_task_lists(&app)?;
+    let now = chrono::Utc::now().to_rfc3339();
+    let report = import_into(&mut tasks, &mut lists, &content, &format, &now);
+    if report.imported > 0 {
+        save_task_lists(&app, &lists)?;
+        save_tasks(&app, &tasks)?;
+    }
+    Ok(report)
+}
+
+#[tauri::command]
+pub fn export_tasks(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    format: String,
+    list_id: Option<String>,
+) -> Result<String, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let format = normalize_transfer_format(&format)?;
+    let lists = load_task_lists(&app)?;
+    let mut tasks = load_tasks(&app)?;
+    tasks.retain(|task| task.deleted_at.is_none());
+    if let Some(list_id) = list_id.as_deref() {
+        tasks.retain(|task| task.list_id == list_id);
+    }
+    sort_tasks(&mut tasks, &lists);
+    Ok(export_as(&tasks, &lists, &format))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: &str = "2026-01-02T09:00:00Z";
+
+    fn import(content: &str, format: &str) -> (Vec<Task>, Vec<TaskList>, TaskImportReport) {
+        let mut tasks = Vec::new();
+        let mut lists = vec![TaskList::inbox()];
+        let report = import_into(&mut tasks, &mut lists, content, format, NOW);
+        (tasks, lists, report)
+    }
+
+    #[test]
+    fn normalize_transfer_format_accepts_aliases() {
+        assert_eq!(normalize_transfer_format("todo.txt").unwrap(), "todotxt");
+        assert_eq!(normalize_transfer_format(" Markdown ").unwrap(), "markdown");
+        assert!(normalize_transfer_format("csv").is_err());
+    }
+
+    #[test]
+    fn parse_todo_txt_line_maps_metadata() {
+        let parsed = parse_todo_txt_line(
+            "(A) 2026-01-01 Review PRs +Work_Stuff @office @Office due:2026-01-05",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed,
+            ParsedTask {
+                title: "Review PRs".to_string(),
+                completed: false,
+                priority: "high".to_string(),
+                due_date: Some("2026-01-05".to_string()),
+                list_name: Some("Work Stuff".to_string()),
+                tags: vec!["office".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_todo_txt_line_handles_completion_and_bad_due_date() {
+        let parsed = parse_todo_txt_line("x 2026-01-02 2026-01-01 Inbox zero").unwrap();
+        assert!(parsed.completed);
+        assert_eq!(parsed.title, "Inbox zero");
+
+        let err = parse_todo_txt_line("Pay rent due:tomorrow").unwrap_err();
+        assert!(err.contains("Invalid due date"));
+    }
+
+    #[test]
+    fn parse_markdown_line_recognizes_items_and_headings() {
+        assert_eq!(
+            parse_markdown_line("- [x] Ship it"),
+            MarkdownLine::Item(ParsedTask {
+                title: "Ship it".to_string(),
+                completed: true,
+                priority: "none".to_string(),
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            parse_markdown_line("## Chores"),
+            MarkdownLine::Heading("Chores".to_string())
+        );
+        assert_eq!(parse_markdown_line("- plain bullet"), MarkdownLine::Other);
+    }
+
+    #[test]
+    fn import_into_reports_each_line_and_skips_duplicates() {
+        let (tasks, lists, report) = import(
+            "Water plants +Home\n\n(B) water plants +home\n   \nx\n",
+            "todotxt",
+        );
+        assert_eq!(report.imported, 1);
+        assert_eq!(report.duplicates, 1);
+        assert_eq!(report.rejected, 1);
+        let line_numbers: Vec<u32> = report.lines.iter().map(|l| l.line).collect();
+        assert_eq!(line_numbers, vec![1, 3, 5]);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(lists.len(), 2);
+        assert_eq!(tasks[0].list_id, lists[1].id);
+    }
+
+    #[test]
+    fn import_into_creates_lists_only_for_accepted_lines() {
+        let mut tasks: Vec<Task> = (0..MAX_TASKS)
+            .map(|i| Task {
+                id: i.to_string(),
+                title: format!("Task {}", i),
+                list_id: INBOX_TASK_LIST_ID.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        let mut lists = vec![TaskList::inbox()];
+        let report = import_into(
+            &mut tasks,
+            &mut lists,
+            "Overflow +Garden
+",
+            "todotxt",
+            NOW,
+        );
+        assert_eq!(report.rejected, 1);
+        assert_eq!(lists.len(), 1);
+    }
+
+    #[test]
+    fn import_into_markdown_uses_headings_as_lists() {
+        let (tasks, lists, report) = import(
+            "# Inbox\n- [ ] Triage mail\n## Chores\n- [x] Dishes\nSome prose\n",
+            "markdown",
+        );
+        assert_eq!(report.imported, 2);
+        assert_eq!(report.lines[4].status, "skipped");
+        assert_eq!(tasks[0].list_id, INBOX_TASK_LIST_ID);
+        assert_eq!(lists[1].name, "Chores");
+        assert!(tasks[1].completed);
+        assert_eq!(tasks[1].completed_at.as_deref(), Some(NOW));
+    }
+
+    #[test]
+    fn export_todo_txt_round_trips_through_import() {
+        let (tasks, lists, _) = import(
+            "(A) Review PRs +Work_Stuff @office due:2026-01-05\nx 2026-01-02 Inbox zero\n",
+            "todotxt",
+        );
+        let exported = export_as(&tasks, &lists, "todotxt");
+        assert_eq!(
+            exported,
+            "(A) Review PRs +Work_Stuff @office due:2026-01-05\nx 2026-01-02 Inbox zero\n"
+        );
+
+        let mut reimported = tasks.clone();
+        let mut relists = lists.clone();
+        let report = import_into(&mut reimported, &mut relists, &exported, "todotxt", NOW);
+        assert_eq!(report.duplicates, 2);
+        assert_eq!(relists.len(), lists.len());
+    }
+
+    #[test]
+    fn export_markdown_groups_by_list() {
+        let (tasks, lists, _) = import("- [ ] A\n## Home\n- [x] B\n", "markdown");
+        assert_eq!(
+            export_as(&tasks, &lists, "markdown"),
+            "## Inbox\n\n- [ ] A\n\n## Home\n\n- [x] B\n"
+        );
+    }
+}
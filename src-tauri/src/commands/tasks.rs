@@ -2,11 +2,34 @@ use serde_json::json;
 use tauri::AppHandle;
 use tauri_plugin_store::StoreExt;
 
-use crate::models::Task;
+use chrono::{Datelike, NaiveDate};
 
+use crate::clock::Clock;
+use crate::models::{
+    ChecklistItem, Task, TaskFilter, TaskList, TaskPatch, TaskRecurrence, INBOX_TASK_LIST_ID,
+};
+
+/// Limit on active tasks; archived and trashed tasks do not count towards it.
+pub(crate) const MAX_TASKS: usize = 2_000;
+/// Limit on everything stored, including archived history and the trash.
+pub(crate) const MAX_STORED_TASKS: usize = 10_000;
+pub(crate) const MAX_TASK_LISTS: usize = 50;
 const MAX_TASK_TITLE_CHARS: usize = 140;
+const MAX_TASK_LIST_NAME_CHARS: usize = 60;
+const MAX_TASK_NOTES_CHARS: usize = 2_000;
+const MAX_TASK_TAGS: usize = 10;
+const MAX_TASK_TAG_CHARS: usize = 32;
+const MAX_CHECKLIST_ITEMS: usize = 50;
+const ALLOWED_TASK_PRIORITIES: &[&str] = &["none", "low", "medium", "high"];
+const ALLOWED_COMPLETION_RULES: &[&str] = &["manual", "auto"];
+const ALLOWED_RECURRENCE_FREQUENCIES: &[&str] = &["daily", "weekdays", "weekly", "interval"];
+const MAX_RECURRENCE_INTERVAL_DAYS: u32 = 365;
+const ALLOWED_EDIT_SCOPES: &[&str] = &["this", "future"];
+const ALLOWED_TASK_VIEWS: &[&str] = &["active", "archived", "trash"];
+const TASK_HOUSEKEEPING_DATE_KEY: &str = "task_housekeeping_date";
+const TASK_LISTS_KEY: &str = "task_lists";
 
-fn normalize_task_title(title: String) -> Result<String, String> {
+pub(crate) fn normalize_task_title(title: String) -> Result<String, String> {
     let normalized = title.trim();
     if normalized.is_empty() {
         return Err("Task title cannot be empty".to_string());
@@ -20,23 +43,740 @@ fn normalize_task_title(title: String) -> Result<String, String> {
     Ok(normalized.to_string())
 }
 
-fn load_tasks(app: &AppHandle) -> Result<Vec<Task>, String> {
+fn normalize_task_priority(priority: String) -> Result<String, String> {
+    let normalized = priority.trim().to_ascii_lowercase();
+    if ALLOWED_TASK_PRIORITIES.contains(&normalized.as_str()) {
+        Ok(normalized)
+    } else {
+        Err(format!("Invalid task priority: {}", priority))
+    }
+}
+
+pub(crate) fn normalize_due_date(due_date: String) -> Result<String, String> {
+    let normalized = due_date.trim();
+    if normalized.is_empty() {
+        return Ok(String::new());
+    }
+    chrono::NaiveDate::parse_from_str(normalized, "%Y-%m-%d")
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .map_err(|_| format!("Invalid due date: {}", normalized))
+}
+
+fn normalize_task_notes(notes: String) -> Result<String, String> {
+    let normalized = notes.trim();
+    if normalized.chars().count() > MAX_TASK_NOTES_CHARS {
+        return Err(format!(
+            "Task notes exceed {} characters",
+            MAX_TASK_NOTES_CHARS
+        ));
+    }
+    Ok(normalized.to_string())
+}
+
+pub(crate) fn normalize_tag(tag: &str) -> Option<String> {
+    let normalized = tag.trim().trim_start_matches('#').to_ascii_lowercase();
+    if normalized.is_empty() || normalized.chars().count() > MAX_TASK_TAG_CHARS {
+        return None;
+    }
+    if !normalized
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
+    {
+        return None;
+    }
+    Some(normalized)
+}
+
+fn normalize_task_tags(tags: Vec<String>) -> Vec<String> {
+    let mut output = Vec::new();
+    for tag in tags {
+        if output.len() >= MAX_TASK_TAGS {
+            break;
+        }
+        let Some(normalized) = normalize_tag(&tag) else {
+            continue;
+        };
+        if !output.contains(&normalized) {
+            output.push(normalized);
+        }
+    }
+    output
+}
+
+fn normalize_recurrence(recurrence: TaskRecurrence) -> Result<TaskRecurrence, String> {
+    let frequency = recurrence.frequency.trim().to_ascii_lowercase();
+    if frequency == "none" {
+        return Ok(TaskRecurrence {
+            frequency,
+            ..Default::default()
+        });
+    }
+    if !ALLOWED_RECURRENCE_FREQUENCIES.contains(&frequency.as_str()) {
+        return Err(format!(
+            "Invalid recurrence frequency: {}",
+            recurrence.frequency
+        ));
+    }
+
+    let mut weekdays = Vec::new();
+    let mut interval_days = 1;
+    match frequency.as_str() {
+        "weekly" => {
+            if let Some(day) = recurrence.weekdays.iter().find(|day| **day > 6) {
+                return Err(format!("Invalid recurrence weekday: {}", day));
+            }
+            weekdays = recurrence.weekdays;
+            weekdays.sort_unstable();
+            weekdays.dedup();
+            if weekdays.is_empty() {
+                return Err("Weekly recurrence needs at least one weekday".to_string());
+            }
+        }
+        "interval" => {
+            if recurrence.interval_days == 0
+                || recurrence.interval_days > MAX_RECURRENCE_INTERVAL_DAYS
+            {
+                return Err(format!(
+                    "Recurrence interval must be between 1 and {} days",
+                    MAX_RECURRENCE_INTERVAL_DAYS
+                ));
+            }
+            interval_days = recurrence.interval_days;
+        }
+        _ => {}
+    }
+
+    Ok(TaskRecurrence {
+        frequency,
+        weekdays,
+        interval_days,
+    })
+}
+
+fn normalize_edit_scope(scope: Option<String>) -> Result<String, String> {
+    let normalized = scope
+        .as_deref()
+        .unwrap_or("this")
+        .trim()
+        .to_ascii_lowercase();
+    if ALLOWED_EDIT_SCOPES.contains(&normalized.as_str()) {
+        Ok(normalized)
+    } else {
+        Err(format!("Invalid edit scope: {}", normalized))
+    }
+}
+
+fn sanitize_task_patch(mut patch: TaskPatch) -> Result<TaskPatch, String> {
+    if let Some(title) = patch.title.take() {
+        patch.title = Some(normalize_task_title(title)?);
+    }
+    if let Some(priority) = patch.priority.take() {
+        patch.priority = Some(normalize_task_priority(priority)?);
+    }
+    if let Some(due_date) = patch.due_date.take() {
+        patch.due_date = Some(normalize_due_date(due_date)?);
+    }
+    if let Some(notes) = patch.notes.take() {
+        patch.notes = Some(normalize_task_notes(notes)?);
+    }
+    if let Some(tags) = patch.tags.take() {
+        patch.tags = Some(normalize_task_tags(tags));
+    }
+    if let Some(rule) = patch.completion_rule.as_deref() {
+        if !ALLOWED_COMPLETION_RULES.contains(&rule) {
+            return Err(format!("Invalid completion rule: {}", rule));
+        }
+    }
+    if let Some(recurrence) = patch.recurrence.take() {
+        patch.recurrence = Some(normalize_recurrence(recurrence)?);
+    }
+    Ok(patch)
+}
+
+/// Repairs task fields that may have been written by older builds or imported snapshots.
+pub(crate) fn sanitize_task(task: &mut Task) {
+    task.title = task
+        .title
+        .trim()
+        .chars()
+        .take(MAX_TASK_TITLE_CHARS)
+        .collect();
+    if normalize_task_priority(task.priority.clone()).is_err() {
+        task.priority = "none".to_string();
+    }
+    task.due_date = task
+        .due_date
+        .take()
+        .and_then(|date| normalize_due_date(date).ok())
+        .filter(|date| !date.is_empty());
+    task.notes = task
+        .notes
+        .trim()
+        .chars()
+        .take(MAX_TASK_NOTES_CHARS)
+        .collect();
+    task.tags = normalize_task_tags(std::mem::take(&mut task.tags));
+    if !task.completed {
+        task.completed_at = None;
+    }
+    if !ALLOWED_COMPLETION_RULES.contains(&task.completion_rule.as_str()) {
+        task.completion_rule = "manual".to_string();
+    }
+    task.checklist
+        .retain(|item| !item.id.is_empty() && !item.title.trim().is_empty());
+    task.checklist.truncate(MAX_CHECKLIST_ITEMS);
+    for item in &mut task.checklist {
+        item.title = item
+            .title
+            .trim()
+            .chars()
+            .take(MAX_TASK_TITLE_CHARS)
+            .collect();
+        if !item.completed {
+            item.completed_at = None;
+        }
+    }
+    task.recurrence = task
+        .recurrence
+        .take()
+        .and_then(|recurrence| normalize_recurrence(recurrence).ok())
+        .filter(|recurrence| recurrence.frequency != "none");
+    if task.recurrence.is_some() && task.series_id.is_none() {
+        task.series_id = Some(task.id.clone());
+    }
+}
+
+pub(crate) fn normalize_task_list_name(name: String) -> Result<String, String> {
+    let normalized = name.trim();
+    if normalized.is_empty() {
+        return Err("List name cannot be empty".to_string());
+    }
+    if normalized.chars().count() > MAX_TASK_LIST_NAME_CHARS {
+        return Err(format!(
+            "List name exceeds {} characters",
+            MAX_TASK_LIST_NAME_CHARS
+        ));
+    }
+    Ok(normalized.to_string())
+}
+
+/// Keeps the Inbox first and drops duplicate or nameless lists.
+pub(crate) fn sanitize_task_lists(lists: &mut Vec<TaskList>) {
+    let mut seen: Vec<String> = Vec::new();
+    lists.retain(|list| {
+        if list.id.is_empty() || list.name.trim().is_empty() || seen.contains(&list.id) {
+            return false;
+        }
+        seen.push(list.id.clone());
+        true
+    });
+    for list in lists.iter_mut() {
+        list.name = list
+            .name
+            .trim()
+            .chars()
+            .take(MAX_TASK_LIST_NAME_CHARS)
+            .collect();
+    }
+    match lists.iter().position(|list| list.id == INBOX_TASK_LIST_ID) {
+        Some(0) => {}
+        Some(idx) => {
+            let inbox = lists.remove(idx);
+            lists.insert(0, inbox);
+        }
+        None => lists.insert(0, TaskList::inbox()),
+    }
+    lists.truncate(MAX_TASK_LISTS);
+}
+
+/// Moves tasks whose list no longer exists back to the Inbox.
+pub(crate) fn reassign_orphaned_tasks(tasks: &mut [Task], lists: &[TaskList]) {
+    for task in tasks.iter_mut() {
+        if !lists.iter().any(|list| list.id == task.list_id) {
+            task.list_id = INBOX_TASK_LIST_ID.to_string();
+        }
+    }
+}
+
+pub(crate) fn next_sort_index(tasks: &[Task], list_id: &str) -> u32 {
+    tasks
+        .iter()
+        .filter(|task| task.list_id == list_id)
+        .map(|task| task.sort_index.saturating_add(1))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Orders tasks by list position, then by sort index. The sort is stable so legacy
+/// tasks that all share index 0 keep their insertion order.
+pub(crate) fn sort_tasks(tasks: &mut [Task], lists: &[TaskList]) {
+    let list_position = |list_id: &str| {
+        lists
+            .iter()
+            .position(|list| list.id == list_id)
+            .unwrap_or(usize::MAX)
+    };
+    tasks.sort_by_key(|task| (list_position(&task.list_id), task.sort_index));
+}
+
+/// Renumbers the tasks of one list so `ordered_ids` come first; unlisted tasks keep their
+/// relative order after them.
+fn reorder_list_tasks(
+    tasks: &mut [Task],
+    list_id: &str,
+    ordered_ids: &[String],
+) -> Result<(), String> {
+    if let Some(unknown) = ordered_ids.iter().find(|id| {
+        !tasks
+            .iter()
+            .any(|task| &task.id == *id && task.list_id == list_id)
+    }) {
+        return Err(format!("Task not found in list: {}", unknown));
+    }
+
+    let mut remaining: Vec<usize> = (0..tasks.len())
+        .filter(|idx| tasks[*idx].list_id == list_id)
+        .collect();
+    remaining.sort_by_key(|idx| tasks[*idx].sort_index);
+
+    let mut ordered = Vec::with_capacity(remaining.len());
+    for id in ordered_ids {
+        if let Some(pos) = remaining.iter().position(|idx| &tasks[*idx].id == id) {
+            ordered.push(remaining.remove(pos));
+        }
+    }
+    ordered.extend(remaining);
+
+    for (position, idx) in ordered.into_iter().enumerate() {
+        tasks[idx].sort_index = position as u32;
+    }
+    Ok(())
+}
+
+fn task_view(task: &Task) -> &'static str {
+    if task.deleted_at.is_some() {
+        "trash"
+    } else if task.archived_at.is_some() {
+        "archived"
+    } else {
+        "active"
+    }
+}
+
+const TRASHED_TASK_ERROR: &str = "Restore the task from the trash first";
+
+/// Finds a task that may be changed; trashed tasks are read-only until restored.
+fn editable_task<'a>(tasks: &'a mut [Task], task_id: &str) -> Result<&'a mut Task, String> {
+    let task = tasks
+        .iter_mut()
+        .find(|t| t.id == task_id)
+        .ok_or_else(|| "Task not found".to_string())?;
+    if task.deleted_at.is_some() {
+        return Err(TRASHED_TASK_ERROR.to_string());
+    }
+    Ok(task)
+}
+
+pub(crate) fn active_task_count(tasks: &[Task]) -> usize {
+    tasks
+        .iter()
+        .filter(|task| task_view(task) == "active")
+        .count()
+}
+
+fn normalize_task_view(view: Option<String>) -> Result<Option<String>, String> {
+    let Some(view) = view else {
+        return Ok(None);
+    };
+    let normalized = view.trim().to_ascii_lowercase();
+    if ALLOWED_TASK_VIEWS.contains(&normalized.as_str()) {
+        Ok(Some(normalized))
+    } else {
+        Err(format!("Invalid task view: {}", view))
+    }
+}
+
+/// Whole days between the local day of an RFC 3339 timestamp and `today`.
+fn days_since(clock: &Clock, timestamp: &str, today: NaiveDate) -> Option<i64> {
+    let date = parse_date(&clock.day_of_timestamp(timestamp)?)?;
+    Some((today - date).num_days())
+}
+
+/// Archives completed tasks finished at least `days` ago. `days == 0` disables it.
+fn auto_archive_tasks(
+    tasks: &mut [Task],
+    clock: &Clock,
+    today: NaiveDate,
+    days: u32,
+    now: &str,
+) -> usize {
+    if days == 0 {
+        return 0;
+    }
+    let mut archived = 0;
+    for task in tasks.iter_mut() {
+        if task_view(task) != "active" || !task.completed {
+            continue;
+        }
+        let old_enough = task
+            .completed_at
+            .as_deref()
+            .and_then(|at| days_since(clock, at, today))
+            .is_some_and(|age| age >= days as i64);
+        if old_enough {
+            task.archived_at = Some(now.to_string());
+            archived += 1;
+        }
+    }
+    archived
+}
+
+/// Permanently removes trashed tasks older than the retention period. `retention_days == 0`
+/// keeps them forever.
+fn purge_trash(
+    tasks: &mut Vec<Task>,
+    clock: &Clock,
+    today: NaiveDate,
+    retention_days: u32,
+) -> usize {
+    if retention_days == 0 {
+        return 0;
+    }
+    let before = tasks.len();
+    tasks.retain(|task| {
+        task.deleted_at
+            .as_deref()
+            .and_then(|at| days_since(clock, at, today))
+            .map_or(true, |age| age < retention_days as i64)
+    });
+    before - tasks.len()
+}
+
+/// Drops the oldest archived tasks once the store grows past `MAX_STORED_TASKS`.
+fn trim_archived_tasks(tasks: &mut Vec<Task>) {
+    let Some(overflow) = tasks.len().checked_sub(MAX_STORED_TASKS) else {
+        return;
+    };
+    let mut archived: Vec<(String, String)> = tasks
+        .iter()
+        .filter(|task| task_view(task) == "archived")
+        .map(|task| {
+            (
+                task.archived_at.clone().unwrap_or_default(),
+                task.id.clone(),
+            )
+        })
+        .collect();
+    archived.sort();
+    let dropped: Vec<String> = archived
+        .into_iter()
+        .take(overflow)
+        .map(|(_, id)| id)
+        .collect();
+    tasks.retain(|task| !dropped.contains(&task.id));
+}
+
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+/// First date strictly after `after` on which the rule fires.
+fn next_occurrence(recurrence: &TaskRecurrence, after: NaiveDate) -> NaiveDate {
+    match recurrence.frequency.as_str() {
+        "interval" => after + chrono::Duration::days(recurrence.interval_days.max(1) as i64),
+        "weekdays" | "weekly" => {
+            let mut date = after;
+            for _ in 0..7 {
+                date = date.succ_opt().unwrap_or(date);
+                let weekday = date.weekday().num_days_from_monday() as u8;
+                let fires = if recurrence.frequency == "weekdays" {
+                    weekday < 5
+                } else {
+                    recurrence.weekdays.contains(&weekday)
+                };
+                if fires {
+                    return date;
+                }
+            }
+            date
+        }
+        _ => after.succ_opt().unwrap_or(after),
+    }
+}
+
+/// Gives a recurring task without a due date the first occurrence on or after `today`.
+fn ensure_recurring_due_date(task: &mut Task, today: NaiveDate) {
+    if task.due_date.is_some() {
+        return;
+    }
+    if let Some(recurrence) = task.recurrence.as_ref() {
+        let yesterday = today.pred_opt().unwrap_or(today);
+        task.due_date = Some(
+            next_occurrence(recurrence, yesterday)
+                .format("%Y-%m-%d")
+                .to_string(),
+        );
+    }
+}
+
+/// Builds the instance that follows `task`, due on the next occurrence after its due date.
+/// Overdue instances are followed by an occurrence no earlier than `today`.
+fn next_instance(task: &Task, today: NaiveDate) -> Option<Task> {
+    let recurrence = task.recurrence.as_ref()?;
+    let yesterday = today.pred_opt().unwrap_or(today);
+    let anchor = task
+        .due_date
+        .as_deref()
+        .and_then(parse_date)
+        .unwrap_or(yesterday)
+        .max(yesterday);
+    let due_date = next_occurrence(recurrence, anchor);
+
+    Some(Task {
+        id: uuid::Uuid::new_v4().to_string(),
+        title: task.title.clone(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        priority: task.priority.clone(),
+        due_date: Some(due_date.format("%Y-%m-%d").to_string()),
+        notes: task.notes.clone(),
+        tags: task.tags.clone(),
+        checklist: task
+            .checklist
+            .iter()
+            .map(|item| ChecklistItem {
+                id: uuid::Uuid::new_v4().to_string(),
+                title: item.title.clone(),
+                completed: false,
+                completed_at: None,
+            })
+            .collect(),
+        completion_rule: task.completion_rule.clone(),
+        recurrence: Some(recurrence.clone()),
+        series_id: task.series_id.clone().or_else(|| Some(task.id.clone())),
+        list_id: task.list_id.clone(),
+        ..Default::default()
+    })
+}
+
+/// Appends the next instance for every series whose latest instance is completed.
+/// With `at_rollover`, a series whose latest instance is open but overdue also gets the
+/// instance that falls due today, so a missed chore doesn't stop the series.
+/// Idempotent: once the new instance exists it is the latest and not yet overdue.
+fn spawn_recurring_instances(tasks: &mut Vec<Task>, today: NaiveDate, at_rollover: bool) -> usize {
+    let mut latest: Vec<(String, usize)> = Vec::new();
+    for (idx, task) in tasks.iter().enumerate() {
+        let Some(series_id) = task.series_id.as_deref() else {
+            continue;
+        };
+        match latest.iter_mut().find(|(id, _)| id == series_id) {
+            Some(entry) => {
+                if tasks[entry.1].due_date <= task.due_date {
+                    entry.1 = idx;
+                }
+            }
+            None => latest.push((series_id.to_string(), idx)),
+        }
+    }
+
+    let today_text = today.format("%Y-%m-%d").to_string();
+    // A series whose latest instance was trashed has been stopped by the user.
+    let spawned: Vec<Task> = latest
+        .into_iter()
+        .filter(|(_, idx)| tasks[*idx].deleted_at.is_none())
+        .filter_map(|(_, idx)| {
+            let latest = &tasks[idx];
+            if latest.completed {
+                return next_instance(latest, today);
+            }
+            let overdue = latest
+                .due_date
+                .as_deref()
+                .is_some_and(|due| due < today_text.as_str());
+            if !at_rollover || !overdue {
+                return None;
+            }
+            next_instance(latest, today)
+                .filter(|next| next.due_date.as_deref() <= Some(today_text.as_str()))
+        })
+        .take(MAX_TASKS.saturating_sub(active_task_count(tasks)))
+        .collect();
+    let count = spawned.len();
+    for mut task in spawned {
+        task.sort_index = next_sort_index(tasks, &task.list_id);
+        tasks.push(task);
+    }
+    count
+}
+
+/// Indexes of the open instances an edit applies to: just `task_id`, or for the "future"
+/// scope every open instance of its series due on or after it.
+fn edit_targets(tasks: &[Task], task_id: &str, scope: &str) -> Result<Vec<usize>, String> {
+    let idx = tasks
+        .iter()
+        .position(|t| t.id == task_id)
+        .ok_or_else(|| "Task not found".to_string())?;
+    let selected = &tasks[idx];
+    if selected.deleted_at.is_some() {
+        return Err(TRASHED_TASK_ERROR.to_string());
+    }
+    let Some(series_id) = selected.series_id.as_deref().filter(|_| scope == "future") else {
+        return Ok(vec![idx]);
+    };
+
+    Ok(tasks
+        .iter()
+        .enumerate()
+        .filter(|(i, task)| {
+            *i == idx
+                || (task.series_id.as_deref() == Some(series_id)
+                    && !task.completed
+                    && task_view(task) == "active"
+                    && task.due_date >= selected.due_date)
+        })
+        .map(|(i, _)| i)
+        .collect())
+}
+
+fn task_matches_filter(task: &Task, filter: &TaskFilter) -> bool {
+    if task_view(task) != filter.view.as_deref().unwrap_or("active") {
+        return false;
+    }
+    if let Some(list_id) = filter.list_id.as_deref() {
+        if task.list_id != list_id {
+            return false;
+        }
+    }
+    if let Some(tag) = filter.tag.as_deref().and_then(normalize_tag) {
+        if !task.tags.contains(&tag) {
+            return false;
+        }
+    }
+    if filter.due_from.is_none() && filter.due_to.is_none() {
+        return true;
+    }
+    let Some(due_date) = task.due_date.as_deref() else {
+        return false;
+    };
+    if let Some(from) = filter.due_from.as_deref() {
+        if due_date < from {
+            return false;
+        }
+    }
+    if let Some(to) = filter.due_to.as_deref() {
+        if due_date > to {
+            return false;
+        }
+    }
+    true
+}
+
+fn filter_tasks(tasks: Vec<Task>, filter: &TaskFilter) -> Vec<Task> {
+    tasks
+        .into_iter()
+        .filter(|task| task_matches_filter(task, filter))
+        .collect()
+}
+
+pub(crate) fn load_tasks(app: &AppHandle) -> Result<Vec<Task>, String> {
     let store = app.store("store.json").map_err(|e| e.to_string())?;
-    Ok(store
+    let mut tasks: Vec<Task> = store
         .get("tasks")
         .and_then(|v| serde_json::from_value(v).ok())
-        .unwrap_or_default())
+        .unwrap_or_default();
+    backfill_created_at(&mut tasks);
+    Ok(tasks)
+}
+
+/// Gives tasks saved without a creation time the earliest timestamp they carry, or the
+/// Unix epoch, so the value is the same on every load.
+fn backfill_created_at(tasks: &mut [Task]) {
+    for task in tasks.iter_mut().filter(|task| task.created_at.is_empty()) {
+        task.created_at = [&task.completed_at, &task.archived_at, &task.deleted_at]
+            .into_iter()
+            .flatten()
+            .min()
+            .cloned()
+            .unwrap_or_else(|| chrono::DateTime::UNIX_EPOCH.to_rfc3339());
+    }
 }
 
-fn save_tasks(app: &AppHandle, tasks: &[Task]) -> Result<(), String> {
+pub(crate) fn save_tasks(app: &AppHandle, tasks: &[Task]) -> Result<(), String> {
     let store = app.store("store.json").map_err(|e| e.to_string())?;
     store.set("tasks", json!(tasks));
     Ok(())
 }
 
+pub(crate) fn load_task_lists(app: &AppHandle) -> Result<Vec<TaskList>, String> {
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    let mut lists: Vec<TaskList> = store
+        .get(TASK_LISTS_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    sanitize_task_lists(&mut lists);
+    Ok(lists)
+}
+
+pub(crate) fn save_task_lists(app: &AppHandle, lists: &[TaskList]) -> Result<(), String> {
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    store.set(TASK_LISTS_KEY, json!(lists));
+    Ok(())
+}
+
+/// Runs once per day: generates the next instance of recurring series completed before
+/// the rollover, archives old completed tasks and purges the trash.
+fn run_daily_housekeeping(app: &AppHandle, tasks: &mut Vec<Task>) -> Result<(), String> {
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    let today = crate::clock::today(app);
+    let last_checked: Option<String> = store
+        .get(TASK_HOUSEKEEPING_DATE_KEY)
+        .and_then(|v| serde_json::from_value(v).ok());
+    if last_checked.as_deref() == Some(today.as_str()) {
+        return Ok(());
+    }
+
+    let settings = crate::commands::settings::get_settings(app.clone())?;
+    let clock = Clock::from_settings(&settings);
+    let date = clock.today();
+    let now = chrono::Utc::now().to_rfc3339();
+    let before = tasks.len();
+    let changed = spawn_recurring_instances(tasks, date, true)
+        + auto_archive_tasks(tasks, &clock, date, settings.task_auto_archive_days, &now)
+        + purge_trash(tasks, &clock, date, settings.task_trash_retention_days);
+    trim_archived_tasks(tasks);
+    if changed > 0 || tasks.len() != before {
+        save_tasks(app, tasks)?;
+    }
+    store.set(TASK_HOUSEKEEPING_DATE_KEY, json!(today));
+    Ok(())
+}
+
+/// Active tasks in list order, as returned by commands that hand back the task list.
+fn visible_tasks(app: &AppHandle, mut tasks: Vec<Task>) -> Result<Vec<Task>, String> {
+    let lists = load_task_lists(app)?;
+    reassign_orphaned_tasks(&mut tasks, &lists);
+    tasks.retain(|task| task_view(task) == "active");
+    sort_tasks(&mut tasks, &lists);
+    Ok(tasks)
+}
+
 #[tauri::command]
-pub fn get_tasks(app: AppHandle) -> Result<Vec<Task>, String> {
-    load_tasks(&app)
+pub fn get_tasks(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    filter: Option<TaskFilter>,
+) -> Result<Vec<Task>, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let mut tasks = load_tasks(&app)?;
+    run_daily_housekeeping(&app, &mut tasks)?;
+    let lists = load_task_lists(&app)?;
+    reassign_orphaned_tasks(&mut tasks, &lists);
+    sort_tasks(&mut tasks, &lists);
+
+    let mut filter = filter.unwrap_or_default();
+    filter.view = normalize_task_view(filter.view)?;
+    filter.due_from = filter.due_from.map(normalize_due_date).transpose()?;
+    filter.due_to = filter.due_to.map(normalize_due_date).transpose()?;
+    Ok(filter_tasks(tasks, &filter))
 }
 
 #[tauri::command]
@@ -44,82 +784,1234 @@ pub fn add_task(
     app: AppHandle,
     store_lock: tauri::State<'_, crate::StoreLock>,
     title: String,
+    details: Option<TaskPatch>,
+    list_id: Option<String>,
 ) -> Result<Task, String> {
     let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
     let mut tasks = load_tasks(&app)?;
+    if active_task_count(&tasks) >= MAX_TASKS {
+        return Err(format!("Cannot have more than {} tasks", MAX_TASKS));
+    }
     let title = normalize_task_title(title)?;
-    let task = Task {
+    let details = sanitize_task_patch(details.unwrap_or_default())?;
+    let list_id = list_id.unwrap_or_else(|| INBOX_TASK_LIST_ID.to_string());
+    if !load_task_lists(&app)?.iter().any(|list| list.id == list_id) {
+        return Err("Task list not found".to_string());
+    }
+    let mut task = Task {
         id: uuid::Uuid::new_v4().to_string(),
         title,
         completed: false,
         created_at: chrono::Utc::now().to_rfc3339(),
+        sort_index: next_sort_index(&tasks, &list_id),
+        list_id,
+        ..Default::default()
     };
+    details.apply_to(&mut task);
+    ensure_recurring_due_date(&mut task, crate::clock::today_date(&app));
     tasks.push(task.clone());
     save_tasks(&app, &tasks)?;
     Ok(task)
 }
 
 #[tauri::command]
-pub fn toggle_task(
+pub fn update_task(
     app: AppHandle,
     store_lock: tauri::State<'_, crate::StoreLock>,
     task_id: String,
-) -> Result<Vec<Task>, String> {
+    patch: TaskPatch,
+    scope: Option<String>,
+) -> Result<Task, String> {
     let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
     let mut tasks = load_tasks(&app)?;
-    let was_incomplete = tasks
-        .iter()
-        .find(|t| t.id == task_id)
-        .map(|t| !t.completed)
-        .unwrap_or(false);
-
-    if let Some(task) = tasks.iter_mut().find(|t| t.id == task_id) {
-        task.completed = !task.completed;
-    }
-    save_tasks(&app, &tasks)?;
+    let patch = sanitize_task_patch(patch)?;
+    let scope = normalize_edit_scope(scope)?;
+    let today = crate::clock::today_date(&app);
 
-    // If task was just completed, increment goal
-    if was_incomplete {
-        let _ = crate::commands::goals::increment_goal_progress(&app, "tasks");
-        let _ = crate::progression::record_task_completion(&app);
+    for idx in edit_targets(&tasks, &task_id, &scope)? {
+        let task = &mut tasks[idx];
+        if task.id == task_id {
+            patch.clone().apply_to(task);
+        } else {
+            // Each instance keeps its own due date.
+            TaskPatch {
+                due_date: None,
+                ..patch.clone()
+            }
+            .apply_to(task);
+        }
+        ensure_recurring_due_date(task, today);
     }
 
-    Ok(tasks)
+    let updated = tasks
+        .iter()
+        .find(|t| t.id == task_id)
+        .cloned()
+        .ok_or_else(|| "Task not found".to_string())?;
+    save_tasks(&app, &tasks)?;
+    Ok(updated)
 }
 
 #[tauri::command]
-pub fn delete_task(
+pub fn toggle_task(
     app: AppHandle,
     store_lock: tauri::State<'_, crate::StoreLock>,
     task_id: String,
 ) -> Result<Vec<Task>, String> {
     let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
     let mut tasks = load_tasks(&app)?;
-    tasks.retain(|t| t.id != task_id);
+    if tasks
+        .iter()
+        .any(|t| t.id == task_id && t.deleted_at.is_some())
+    {
+        return Err(TRASHED_TASK_ERROR.to_string());
+    }
+    let now_completed = tasks.iter_mut().find(|t| t.id == task_id).map(|task| {
+        set_task_completed(task, !task.completed);
+        task.completed
+    });
+    if now_completed == Some(true) {
+        spawn_recurring_instances(&mut tasks, crate::clock::today_date(&app), false);
+    }
     save_tasks(&app, &tasks)?;
-    Ok(tasks)
+
+    if let Some(completed) = now_completed {
+        settle_task_rewards(&app, &task_id, completed);
+    }
+
+    visible_tasks(&app, tasks)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn set_task_completed(task: &mut Task, completed: bool) {
+    task.completed = completed;
+    task.completed_at = if completed {
+        Some(chrono::Utc::now().to_rfc3339())
+    } else {
+        None
+    };
+}
 
-    #[test]
-    fn normalize_task_title_trims() {
-        let title = normalize_task_title("  ship release  ".to_string()).unwrap();
-        assert_eq!(title, "ship release");
+fn clawback_enabled(app: &AppHandle) -> bool {
+    crate::commands::settings::get_settings(app.clone())
+        .map(|settings| settings.task_reward_clawback_enabled)
+        .unwrap_or(false)
+}
+
+fn settle_task_rewards(app: &AppHandle, task_id: &str, completed: bool) {
+    if completed {
+        let _ = reward_task_completion(app, task_id);
+    } else if clawback_enabled(app) {
+        let _ = claw_back_task_completion(app, task_id);
     }
+    let _ = crate::commands::period_goals::refresh_period_goals(app);
+}
 
-    #[test]
-    fn normalize_task_title_rejects_empty() {
-        let err = normalize_task_title("   ".to_string()).unwrap_err();
-        assert!(err.contains("cannot be empty"));
+/// Credits goal progress and XP through the reward ledger so re-toggling a task pays nothing extra.
+fn reward_task_completion(app: &AppHandle, task_id: &str) -> Result<(), String> {
+    if crate::rewards::claim(
+        app,
+        crate::rewards::SOURCE_TASK,
+        task_id,
+        crate::rewards::REWARD_GOAL_PROGRESS,
+        1,
+    )? {
+        crate::commands::goals::increment_goal_progress(app, "tasks")?;
+    }
+    if crate::rewards::claim(
+        app,
+        crate::rewards::SOURCE_TASK,
+        task_id,
+        crate::rewards::REWARD_XP,
+        crate::progression::XP_PER_TASK_COMPLETION,
+    )? {
+        crate::progression::record_task_completion(app)?;
     }
+    Ok(())
+}
 
-    #[test]
-    fn normalize_task_title_rejects_too_long() {
-        let too_long = "x".repeat(MAX_TASK_TITLE_CHARS + 1);
-        let err = normalize_task_title(too_long).unwrap_err();
-        assert!(err.contains("exceeds"));
+fn claw_back_task_completion(app: &AppHandle, task_id: &str) -> Result<(), String> {
+    if let Some(entry) = crate::rewards::revoke(
+        app,
+        crate::rewards::SOURCE_TASK,
+        task_id,
+        crate::rewards::REWARD_GOAL_PROGRESS,
+    )? {
+        if let Some(credited_on) = crate::clock::day_of_timestamp(app, &entry.granted_at) {
+            crate::commands::goals::remove_goal_progress(app, "tasks", &credited_on, entry.amount)?;
+        }
+    }
+    if let Some(entry) = crate::rewards::revoke(
+        app,
+        crate::rewards::SOURCE_TASK,
+        task_id,
+        crate::rewards::REWARD_XP,
+    )? {
+        crate::progression::revoke_task_completion(app, &entry.granted_at, entry.amount)?;
+    }
+    Ok(())
+}
+
+/// Subtask XP is keyed by item id, so renaming an item never pays it again.
+fn subtask_reward_source_id(task_id: &str, item_id: &str) -> String {
+    format!("{}:{}", task_id, item_id)
+}
+
+/// Active subtask XP payouts for `task_id`, across every key it has used.
+fn subtask_rewards_paid(ledger: &[crate::models::RewardLedgerEntry], task_id: &str) -> usize {
+    let prefix = format!("{}:", task_id);
+    ledger
+        .iter()
+        .filter(|entry| {
+            entry.source_kind == crate::rewards::SOURCE_SUBTASK
+                && entry.reward_kind == crate::rewards::REWARD_XP
+                && entry.revoked_at.is_none()
+                && entry.source_id.starts_with(&prefix)
+        })
+        .count()
+}
+
+/// Outcome of toggling a checklist item, including any automatic change to the parent task.
+#[derive(Debug, PartialEq)]
+struct ChecklistToggle {
+    item_completed: bool,
+    parent_completed: Option<bool>,
+}
+
+fn toggle_checklist_item_in(task: &mut Task, item_id: &str) -> Result<ChecklistToggle, String> {
+    let item = task
+        .checklist
+        .iter_mut()
+        .find(|item| item.id == item_id)
+        .ok_or_else(|| "Checklist item not found".to_string())?;
+    item.completed = !item.completed;
+    item.completed_at = if item.completed {
+        Some(chrono::Utc::now().to_rfc3339())
+    } else {
+        None
+    };
+    let item_completed = item.completed;
+
+    Ok(ChecklistToggle {
+        item_completed,
+        parent_completed: apply_completion_rule(task),
+    })
+}
+
+/// Completes or reopens an `auto` task to match its checklist, returning the new state
+/// when it changed. An empty checklist leaves the task alone.
+fn apply_completion_rule(task: &mut Task) -> Option<bool> {
+    if task.completion_rule != "auto" || task.checklist.is_empty() {
+        return None;
+    }
+    let all_done = task.checklist.iter().all(|item| item.completed);
+    if all_done == task.completed {
+        return None;
+    }
+    set_task_completed(task, all_done);
+    Some(all_done)
+}
+
+/// Reorders checklist items by id. Items missing from `ordered_ids` keep their relative order at the end.
+fn reorder_checklist(
+    items: Vec<ChecklistItem>,
+    ordered_ids: &[String],
+) -> Result<Vec<ChecklistItem>, String> {
+    if let Some(unknown) = ordered_ids
+        .iter()
+        .find(|id| !items.iter().any(|item| &item.id == *id))
+    {
+        return Err(format!("Checklist item not found: {}", unknown));
+    }
+
+    let mut remaining = items;
+    let mut ordered = Vec::with_capacity(remaining.len());
+    for id in ordered_ids {
+        if let Some(idx) = remaining.iter().position(|item| &item.id == id) {
+            ordered.push(remaining.remove(idx));
+        }
+    }
+    ordered.extend(remaining);
+    Ok(ordered)
+}
+
+/// Pays subtask XP on an item's first completion, and for at most `MAX_CHECKLIST_ITEMS` items per task.
+fn reward_subtask_completion(app: &AppHandle, task_id: &str, item_id: &str) -> Result<(), String> {
+    if subtask_rewards_paid(&crate::rewards::load_ledger(app)?, task_id) >= MAX_CHECKLIST_ITEMS {
+        return Ok(());
+    }
+    if crate::rewards::claim(
+        app,
+        crate::rewards::SOURCE_SUBTASK,
+        &subtask_reward_source_id(task_id, item_id),
+        crate::rewards::REWARD_XP,
+        crate::progression::XP_PER_SUBTASK_COMPLETION,
+    )? {
+        crate::progression::record_subtask_completion(app)?;
+    }
+    Ok(())
+}
+
+fn claw_back_subtask_completion(
+    app: &AppHandle,
+    task_id: &str,
+    item_id: &str,
+) -> Result<(), String> {
+    if let Some(entry) = crate::rewards::revoke(
+        app,
+        crate::rewards::SOURCE_SUBTASK,
+        &subtask_reward_source_id(task_id, item_id),
+        crate::rewards::REWARD_XP,
+    )? {
+        crate::progression::revoke_subtask_completion(app, &entry.granted_at, entry.amount)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn add_checklist_item(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    task_id: String,
+    title: String,
+) -> Result<Task, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let mut tasks = load_tasks(&app)?;
+    let title = normalize_task_title(title)?;
+    let task = editable_task(&mut tasks, &task_id)?;
+    if task.checklist.len() >= MAX_CHECKLIST_ITEMS {
+        return Err(format!(
+            "Task cannot have more than {} checklist items",
+            MAX_CHECKLIST_ITEMS
+        ));
+    }
+    task.checklist.push(ChecklistItem {
+        id: uuid::Uuid::new_v4().to_string(),
+        title,
+        completed: false,
+        completed_at: None,
+    });
+    let parent_completed = apply_completion_rule(task);
+    let updated = task.clone();
+    save_tasks(&app, &tasks)?;
+
+    if let Some(completed) = parent_completed {
+        settle_task_rewards(&app, &task_id, completed);
+    }
+    Ok(updated)
+}
+
+#[tauri::command]
+pub fn toggle_checklist_item(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    task_id: String,
+    item_id: String,
+) -> Result<Task, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let mut tasks = load_tasks(&app)?;
+    let task = editable_task(&mut tasks, &task_id)?;
+    let toggle = toggle_checklist_item_in(task, &item_id)?;
+    let updated = task.clone();
+    if toggle.parent_completed == Some(true) {
+        spawn_recurring_instances(&mut tasks, crate::clock::today_date(&app), false);
+    }
+    save_tasks(&app, &tasks)?;
+
+    if toggle.item_completed {
+        let _ = reward_subtask_completion(&app, &task_id, &item_id);
+    } else if clawback_enabled(&app) {
+        let _ = claw_back_subtask_completion(&app, &task_id, &item_id);
+    }
+    if let Some(completed) = toggle.parent_completed {
+        settle_task_rewards(&app, &task_id, completed);
+    }
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub fn reorder_checklist_items(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    task_id: String,
+    item_ids: Vec<String>,
+) -> Result<Task, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let mut tasks = load_tasks(&app)?;
+    let task = editable_task(&mut tasks, &task_id)?;
+    task.checklist = reorder_checklist(std::mem::take(&mut task.checklist), &item_ids)?;
+    let updated = task.clone();
+    save_tasks(&app, &tasks)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+pub fn delete_checklist_item(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    task_id: String,
+    item_id: String,
+) -> Result<Task, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let mut tasks = load_tasks(&app)?;
+    let task = editable_task(&mut tasks, &task_id)?;
+    task.checklist.retain(|item| item.id != item_id);
+    let parent_completed = apply_completion_rule(task);
+    let updated = task.clone();
+    if parent_completed == Some(true) {
+        spawn_recurring_instances(&mut tasks, crate::clock::today_date(&app), false);
+    }
+    save_tasks(&app, &tasks)?;
+
+    if let Some(completed) = parent_completed {
+        settle_task_rewards(&app, &task_id, completed);
+    }
+    Ok(updated)
+}
+
+#[tauri::command]
+pub fn get_task_lists(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+) -> Result<Vec<TaskList>, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    load_task_lists(&app)
+}
+
+#[tauri::command]
+pub fn create_task_list(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    name: String,
+) -> Result<TaskList, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let mut lists = load_task_lists(&app)?;
+    if lists.len() >= MAX_TASK_LISTS {
+        return Err(format!(
+            "Cannot have more than {} task lists",
+            MAX_TASK_LISTS
+        ));
+    }
+    let list = TaskList {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: normalize_task_list_name(name)?,
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+    lists.push(list.clone());
+    save_task_lists(&app, &lists)?;
+    Ok(list)
+}
+
+#[tauri::command]
+pub fn rename_task_list(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    list_id: String,
+    name: String,
+) -> Result<TaskList, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let mut lists = load_task_lists(&app)?;
+    let name = normalize_task_list_name(name)?;
+    let list = lists
+        .iter_mut()
+        .find(|list| list.id == list_id)
+        .ok_or_else(|| "Task list not found".to_string())?;
+    list.name = name;
+    let updated = list.clone();
+    save_task_lists(&app, &lists)?;
+    Ok(updated)
+}
+
+/// Deletes a list and moves its tasks to the end of the Inbox.
+#[tauri::command]
+pub fn delete_task_list(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    list_id: String,
+) -> Result<Vec<TaskList>, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    if list_id == INBOX_TASK_LIST_ID {
+        return Err("The Inbox cannot be deleted".to_string());
+    }
+    let mut lists = load_task_lists(&app)?;
+    if !lists.iter().any(|list| list.id == list_id) {
+        return Err("Task list not found".to_string());
+    }
+    lists.retain(|list| list.id != list_id);
+
+    let mut tasks = load_tasks(&app)?;
+    let mut moved: Vec<usize> = (0..tasks.len())
+        .filter(|idx| tasks[*idx].list_id == list_id)
+        .collect();
+    moved.sort_by_key(|idx| tasks[*idx].sort_index);
+    for idx in moved {
+        tasks[idx].sort_index = next_sort_index(&tasks, INBOX_TASK_LIST_ID);
+        tasks[idx].list_id = INBOX_TASK_LIST_ID.to_string();
+    }
+
+    save_tasks(&app, &tasks)?;
+    save_task_lists(&app, &lists)?;
+    Ok(lists)
+}
+
+#[tauri::command]
+pub fn move_task_to_list(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    task_id: String,
+    list_id: String,
+) -> Result<Task, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    if !load_task_lists(&app)?.iter().any(|list| list.id == list_id) {
+        return Err("Task list not found".to_string());
+    }
+    let mut tasks = load_tasks(&app)?;
+    let sort_index = next_sort_index(&tasks, &list_id);
+    let task = editable_task(&mut tasks, &task_id)?;
+    if task.list_id != list_id {
+        task.list_id = list_id;
+        task.sort_index = sort_index;
+    }
+    let updated = task.clone();
+    save_tasks(&app, &tasks)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+pub fn reorder_tasks(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    list_id: String,
+    task_ids: Vec<String>,
+) -> Result<Vec<Task>, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let lists = load_task_lists(&app)?;
+    let mut tasks = load_tasks(&app)?;
+    reassign_orphaned_tasks(&mut tasks, &lists);
+    reorder_list_tasks(&mut tasks, &list_id, &task_ids)?;
+    save_tasks(&app, &tasks)?;
+    visible_tasks(&app, tasks)
+}
+
+#[tauri::command]
+pub fn delete_task(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    task_id: String,
+) -> Result<Vec<Task>, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let mut tasks = load_tasks(&app)?;
+    if let Some(task) = tasks.iter_mut().find(|t| t.id == task_id) {
+        task.deleted_at
+            .get_or_insert_with(|| chrono::Utc::now().to_rfc3339());
+    }
+    save_tasks(&app, &tasks)?;
+    visible_tasks(&app, tasks)
+}
+
+fn set_task_timestamp(
+    app: &AppHandle,
+    task_id: &str,
+    apply: impl FnOnce(&mut Task) -> Result<(), String>,
+) -> Result<Task, String> {
+    let mut tasks = load_tasks(app)?;
+    let task = tasks
+        .iter_mut()
+        .find(|t| t.id == task_id)
+        .ok_or_else(|| "Task not found".to_string())?;
+    apply(task)?;
+    let updated = task.clone();
+    save_tasks(app, &tasks)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+pub fn archive_task(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    task_id: String,
+) -> Result<Task, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    set_task_timestamp(&app, &task_id, |task| {
+        if task.deleted_at.is_some() {
+            return Err(TRASHED_TASK_ERROR.to_string());
+        }
+        task.archived_at
+            .get_or_insert_with(|| chrono::Utc::now().to_rfc3339());
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn unarchive_task(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    task_id: String,
+) -> Result<Task, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let tasks = load_tasks(&app)?;
+    if active_task_count(&tasks) >= MAX_TASKS {
+        return Err(format!("Cannot have more than {} tasks", MAX_TASKS));
+    }
+    set_task_timestamp(&app, &task_id, |task| {
+        if task.deleted_at.is_some() {
+            return Err(TRASHED_TASK_ERROR.to_string());
+        }
+        task.archived_at = None;
+        Ok(())
+    })
+}
+
+/// Brings a task back from the trash into the view it was deleted from.
+#[tauri::command]
+pub fn restore_task(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+    task_id: String,
+) -> Result<Task, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let tasks = load_tasks(&app)?;
+    let restores_to_active = tasks
+        .iter()
+        .any(|t| t.id == task_id && t.archived_at.is_none());
+    if restores_to_active && active_task_count(&tasks) >= MAX_TASKS {
+        return Err(format!("Cannot have more than {} tasks", MAX_TASKS));
+    }
+    set_task_timestamp(&app, &task_id, |task| {
+        task.deleted_at = None;
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn empty_trash(
+    app: AppHandle,
+    store_lock: tauri::State<'_, crate::StoreLock>,
+) -> Result<u32, String> {
+    let _guard = store_lock.0.lock().map_err(|e| e.to_string())?;
+    let mut tasks = load_tasks(&app)?;
+    let before = tasks.len();
+    tasks.retain(|task| task.deleted_at.is_none());
+    let purged = before - tasks.len();
+    save_tasks(&app, &tasks)?;
+    Ok(purged as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task_with(id: &str, tags: &[&str], due_date: Option<&str>) -> Task {
+        Task {
+            id: id.to_string(),
+            title: format!("Task {id}"),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            due_date: due_date.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        parse_date(value).unwrap()
+    }
+
+    fn utc_clock() -> Clock {
+        Clock::new(Some(chrono_tz::UTC), 0)
+    }
+
+    fn recurrence(frequency: &str, weekdays: &[u8], interval_days: u32) -> TaskRecurrence {
+        TaskRecurrence {
+            frequency: frequency.to_string(),
+            weekdays: weekdays.to_vec(),
+            interval_days,
+        }
+    }
+
+    fn recurring_task(id: &str, due_date: &str, completed: bool) -> Task {
+        Task {
+            completed,
+            recurrence: Some(recurrence("daily", &[], 1)),
+            series_id: Some("series".to_string()),
+            ..task_with(id, &[], Some(due_date))
+        }
+    }
+
+    fn list(id: &str) -> TaskList {
+        TaskList {
+            id: id.to_string(),
+            name: format!("List {id}"),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    fn listed_task(id: &str, list_id: &str, sort_index: u32) -> Task {
+        Task {
+            list_id: list_id.to_string(),
+            sort_index,
+            ..task_with(id, &[], None)
+        }
+    }
+
+    fn ids(tasks: &[Task]) -> Vec<&str> {
+        tasks.iter().map(|task| task.id.as_str()).collect()
+    }
+
+    #[test]
+    fn sanitize_task_lists_keeps_inbox_first() {
+        let mut lists = vec![list("work"), list("work"), list("")];
+        sanitize_task_lists(&mut lists);
+        assert_eq!(lists.len(), 2);
+        assert_eq!(lists[0].id, INBOX_TASK_LIST_ID);
+        assert_eq!(lists[1].id, "work");
+
+        let mut lists = vec![list("work"), TaskList::inbox()];
+        sanitize_task_lists(&mut lists);
+        assert_eq!(lists[0].id, INBOX_TASK_LIST_ID);
+    }
+
+    #[test]
+    fn reassign_orphaned_tasks_moves_to_inbox() {
+        let mut tasks = vec![listed_task("a", "gone", 0), listed_task("b", "work", 0)];
+        reassign_orphaned_tasks(&mut tasks, &[TaskList::inbox(), list("work")]);
+        assert_eq!(tasks[0].list_id, INBOX_TASK_LIST_ID);
+        assert_eq!(tasks[1].list_id, "work");
+    }
+
+    #[test]
+    fn sort_tasks_groups_by_list_and_is_stable_for_legacy_tasks() {
+        let mut tasks = vec![
+            listed_task("w1", "work", 1),
+            listed_task("i1", INBOX_TASK_LIST_ID, 0),
+            listed_task("w0", "work", 0),
+            listed_task("i2", INBOX_TASK_LIST_ID, 0),
+        ];
+        sort_tasks(&mut tasks, &[TaskList::inbox(), list("work")]);
+        assert_eq!(ids(&tasks), vec!["i1", "i2", "w0", "w1"]);
+    }
+
+    #[test]
+    fn next_sort_index_appends_to_list() {
+        let tasks = vec![listed_task("a", "work", 4), listed_task("b", "home", 9)];
+        assert_eq!(next_sort_index(&tasks, "work"), 5);
+        assert_eq!(next_sort_index(&tasks, "empty"), 0);
+    }
+
+    #[test]
+    fn reorder_list_tasks_renumbers_only_that_list() {
+        let mut tasks = vec![
+            listed_task("a", "work", 0),
+            listed_task("b", "work", 1),
+            listed_task("c", "work", 2),
+            listed_task("x", "home", 7),
+        ];
+        reorder_list_tasks(&mut tasks, "work", &["c".to_string(), "a".to_string()]).unwrap();
+        sort_tasks(&mut tasks, &[list("work"), list("home")]);
+        assert_eq!(ids(&tasks), vec!["c", "a", "b", "x"]);
+        assert_eq!(tasks[3].sort_index, 7);
+
+        let err = reorder_list_tasks(&mut tasks, "work", &["x".to_string()]).unwrap_err();
+        assert!(err.contains("not found"));
+    }
+
+    #[test]
+    fn filter_tasks_by_list() {
+        let tasks = vec![listed_task("a", "work", 0), listed_task("b", "home", 0)];
+        let filter = TaskFilter {
+            list_id: Some("home".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(ids(&filter_tasks(tasks, &filter)), vec!["b"]);
+    }
+
+    #[test]
+    fn filter_tasks_defaults_to_active_view() {
+        let tasks = vec![
+            task_with("active", &[], None),
+            Task {
+                archived_at: Some("2026-01-01T00:00:00Z".to_string()),
+                ..task_with("archived", &[], None)
+            },
+            Task {
+                archived_at: Some("2026-01-01T00:00:00Z".to_string()),
+                deleted_at: Some("2026-01-02T00:00:00Z".to_string()),
+                ..task_with("trashed", &[], None)
+            },
+        ];
+        assert_eq!(
+            ids(&filter_tasks(tasks.clone(), &TaskFilter::default())),
+            vec!["active"]
+        );
+        let archived = TaskFilter {
+            view: Some("archived".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(&filter_tasks(tasks.clone(), &archived)),
+            vec!["archived"]
+        );
+        let trash = TaskFilter {
+            view: Some("trash".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(ids(&filter_tasks(tasks, &trash)), vec!["trashed"]);
+        assert!(normalize_task_view(Some("deleted".to_string())).is_err());
+    }
+
+    #[test]
+    fn auto_archive_tasks_archives_old_completed_tasks() {
+        let completed_on = |id: &str, at: &str| Task {
+            completed: true,
+            completed_at: Some(at.to_string()),
+            ..task_with(id, &[], None)
+        };
+        let mut tasks = vec![
+            completed_on("old", "2026-01-01T10:00:00Z"),
+            completed_on("recent", "2026-01-06T10:00:00Z"),
+            task_with("open", &[], None),
+        ];
+        let today = date("2026-01-08");
+        let clock = utc_clock();
+        assert_eq!(auto_archive_tasks(&mut tasks, &clock, today, 0, "now"), 0);
+        assert_eq!(auto_archive_tasks(&mut tasks, &clock, today, 7, "now"), 1);
+        assert_eq!(tasks[0].archived_at.as_deref(), Some("now"));
+        assert!(tasks[1].archived_at.is_none());
+        assert!(tasks[2].archived_at.is_none());
+        assert_eq!(auto_archive_tasks(&mut tasks, &clock, today, 7, "later"), 0);
+    }
+
+    #[test]
+    fn purge_trash_respects_retention() {
+        let trashed_on = |id: &str, at: &str| Task {
+            deleted_at: Some(at.to_string()),
+            ..task_with(id, &[], None)
+        };
+        let mut tasks = vec![
+            trashed_on("expired", "2026-01-01T00:00:00Z"),
+            trashed_on("fresh", "2026-01-20T00:00:00Z"),
+            task_with("kept", &[], None),
+        ];
+        assert_eq!(
+            purge_trash(&mut tasks, &utc_clock(), date("2026-01-31"), 0),
+            0
+        );
+        assert_eq!(
+            purge_trash(&mut tasks, &utc_clock(), date("2026-01-31"), 30),
+            1
+        );
+        assert_eq!(ids(&tasks), vec!["fresh", "kept"]);
+    }
+
+    #[test]
+    fn days_since_uses_the_local_day() {
+        let tokyo = Clock::new(Some(chrono_tz::Asia::Tokyo), 0);
+        let today = date("2026-01-02");
+        assert_eq!(days_since(&tokyo, "2026-01-01T20:00:00Z", today), Some(0));
+        assert_eq!(
+            days_since(&utc_clock(), "2026-01-01T20:00:00Z", today),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn spawn_recurring_instances_stops_when_latest_is_trashed() {
+        let mut tasks = vec![Task {
+            deleted_at: Some("2026-01-02T00:00:00Z".to_string()),
+            ..recurring_task("a", "2026-01-02", true)
+        }];
+        assert_eq!(
+            spawn_recurring_instances(&mut tasks, date("2026-01-03"), false),
+            0
+        );
+    }
+
+    #[test]
+    fn active_task_count_ignores_archive_and_trash() {
+        let tasks = vec![
+            task_with("a", &[], None),
+            Task {
+                archived_at: Some("2026-01-01T00:00:00Z".to_string()),
+                ..task_with("b", &[], None)
+            },
+        ];
+        assert_eq!(active_task_count(&tasks), 1);
+    }
+
+    #[test]
+    fn normalize_recurrence_validates_rules() {
+        let weekly = normalize_recurrence(recurrence("Weekly", &[4, 0, 4], 9)).unwrap();
+        assert_eq!(weekly, recurrence("weekly", &[0, 4], 1));
+        assert!(normalize_recurrence(recurrence("weekly", &[], 1)).is_err());
+        assert!(normalize_recurrence(recurrence("weekly", &[7], 1)).is_err());
+        assert!(normalize_recurrence(recurrence("interval", &[], 0)).is_err());
+        assert!(normalize_recurrence(recurrence("hourly", &[], 1)).is_err());
+        assert_eq!(
+            normalize_recurrence(recurrence("none", &[1], 3))
+                .unwrap()
+                .frequency,
+            "none"
+        );
+    }
+
+    #[test]
+    fn next_occurrence_follows_each_frequency() {
+        // 2026-01-02 is a Friday.
+        let friday = date("2026-01-02");
+        assert_eq!(
+            next_occurrence(&recurrence("daily", &[], 1), friday),
+            date("2026-01-03")
+        );
+        assert_eq!(
+            next_occurrence(&recurrence("weekdays", &[], 1), friday),
+            date("2026-01-05")
+        );
+        assert_eq!(
+            next_occurrence(&recurrence("weekly", &[2], 1), friday),
+            date("2026-01-07")
+        );
+        assert_eq!(
+            next_occurrence(&recurrence("weekly", &[4], 1), friday),
+            date("2026-01-09")
+        );
+        assert_eq!(
+            next_occurrence(&recurrence("interval", &[], 3), friday),
+            date("2026-01-05")
+        );
+    }
+
+    #[test]
+    fn spawn_recurring_instances_follows_completed_latest_instance() {
+        let mut tasks = vec![recurring_task("a", "2026-01-02", true)];
+        assert_eq!(
+            spawn_recurring_instances(&mut tasks, date("2026-01-02"), false),
+            1
+        );
+        let next = &tasks[1];
+        assert_eq!(next.due_date.as_deref(), Some("2026-01-03"));
+        assert_eq!(next.series_id.as_deref(), Some("series"));
+        assert!(!next.completed);
+
+        assert_eq!(
+            spawn_recurring_instances(&mut tasks, date("2026-01-02"), false),
+            0
+        );
+        assert_eq!(tasks.len(), 2);
+    }
+
+    #[test]
+    fn spawn_recurring_instances_skips_open_and_overdue_catches_up() {
+        let mut open = vec![recurring_task("a", "2026-01-02", false)];
+        assert_eq!(
+            spawn_recurring_instances(&mut open, date("2026-01-05"), false),
+            0
+        );
+
+        let mut overdue = vec![recurring_task("a", "2026-01-02", true)];
+        spawn_recurring_instances(&mut overdue, date("2026-01-05"), false);
+        assert_eq!(overdue[1].due_date.as_deref(), Some("2026-01-05"));
+    }
+
+    #[test]
+    fn spawn_recurring_instances_at_rollover_replaces_missed_instance() {
+        let mut missed = vec![recurring_task("a", "2026-01-02", false)];
+        assert_eq!(
+            spawn_recurring_instances(&mut missed, date("2026-01-02"), true),
+            0
+        );
+        assert_eq!(
+            spawn_recurring_instances(&mut missed, date("2026-01-04"), true),
+            1
+        );
+        assert_eq!(missed[1].due_date.as_deref(), Some("2026-01-04"));
+        assert!(!missed[0].completed);
+        assert_eq!(
+            spawn_recurring_instances(&mut missed, date("2026-01-04"), true),
+            0
+        );
+
+        // 2026-01-06 is a Tuesday; the next Friday occurrence isn't due yet.
+        let mut weekly = vec![Task {
+            recurrence: Some(recurrence("weekly", &[4], 1)),
+            ..recurring_task("w", "2026-01-02", false)
+        }];
+        assert_eq!(
+            spawn_recurring_instances(&mut weekly, date("2026-01-06"), true),
+            0
+        );
+        assert_eq!(
+            spawn_recurring_instances(&mut weekly, date("2026-01-09"), true),
+            1
+        );
+    }
+
+    #[test]
+    fn edit_targets_future_scope_covers_open_later_instances() {
+        let tasks = vec![
+            recurring_task("done", "2026-01-01", true),
+            recurring_task("a", "2026-01-02", false),
+            recurring_task("b", "2026-01-03", false),
+            task_with("other", &[], Some("2026-01-04")),
+        ];
+        assert_eq!(edit_targets(&tasks, "a", "this").unwrap(), vec![1]);
+        assert_eq!(edit_targets(&tasks, "a", "future").unwrap(), vec![1, 2]);
+        assert!(edit_targets(&tasks, "missing", "this").is_err());
+    }
+
+    #[test]
+    fn trashed_tasks_cannot_be_edited() {
+        let mut tasks = vec![task_with("kept", &[], None), task_with("binned", &[], None)];
+        tasks[1].deleted_at = Some("2026-01-02T00:00:00Z".to_string());
+
+        assert!(editable_task(&mut tasks, "kept").is_ok());
+        assert_eq!(
+            editable_task(&mut tasks, "binned").unwrap_err(),
+            TRASHED_TASK_ERROR
+        );
+        assert!(editable_task(&mut tasks, "missing").is_err());
+        assert!(edit_targets(&tasks, "binned", "this").is_err());
+    }
+
+    #[test]
+    fn normalize_task_title_trims() {
+        let title = normalize_task_title("  ship release  ".to_string()).unwrap();
+        assert_eq!(title, "ship release");
+    }
+
+    #[test]
+    fn normalize_task_title_rejects_empty() {
+        let err = normalize_task_title("   ".to_string()).unwrap_err();
+        assert!(err.contains("cannot be empty"));
+    }
+
+    #[test]
+    fn normalize_task_title_rejects_too_long() {
+        let too_long = "x".repeat(MAX_TASK_TITLE_CHARS + 1);
+        let err = normalize_task_title(too_long).unwrap_err();
+        assert!(err.contains("exceeds"));
+    }
+
+    #[test]
+    fn normalize_task_priority_accepts_known_levels() {
+        assert_eq!(
+            normalize_task_priority(" High ".to_string()).unwrap(),
+            "high"
+        );
+        let err = normalize_task_priority("urgent".to_string()).unwrap_err();
+        assert!(err.contains("Invalid task priority"));
+    }
+
+    #[test]
+    fn normalize_due_date_validates_format() {
+        assert_eq!(
+            normalize_due_date("2026-03-01".to_string()).unwrap(),
+            "2026-03-01"
+        );
+        assert_eq!(normalize_due_date("  ".to_string()).unwrap(), "");
+        assert!(normalize_due_date("2026-02-30".to_string()).is_err());
+        assert!(normalize_due_date("tomorrow".to_string()).is_err());
+    }
+
+    #[test]
+    fn normalize_task_tags_deduplicates_and_filters_invalid() {
+        let tags = normalize_task_tags(vec![
+            "#Work".to_string(),
+            "work".to_string(),
+            "deep focus".to_string(),
+            "side-project".to_string(),
+        ]);
+        assert_eq!(tags, vec!["work".to_string(), "side-project".to_string()]);
+    }
+
+    #[test]
+    fn sanitize_task_patch_rejects_long_notes() {
+        let err = sanitize_task_patch(TaskPatch {
+            notes: Some("x".repeat(MAX_TASK_NOTES_CHARS + 1)),
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert!(err.contains("notes exceed"));
+    }
+
+    #[test]
+    fn sanitize_task_repairs_invalid_fields() {
+        let mut task = Task {
+            title: "  Legacy  ".to_string(),
+            priority: "urgent".to_string(),
+            due_date: Some("not-a-date".to_string()),
+            tags: vec!["A".to_string(), "a".to_string()],
+            completed_at: Some("2026-01-01T00:00:00Z".to_string()),
+            ..Default::default()
+        };
+        sanitize_task(&mut task);
+        assert_eq!(task.title, "Legacy");
+        assert_eq!(task.priority, "none");
+        assert!(task.due_date.is_none());
+        assert_eq!(task.tags, vec!["a".to_string()]);
+        assert!(task.completed_at.is_none());
+    }
+
+    fn checklist_item(id: &str, completed: bool) -> ChecklistItem {
+        ChecklistItem {
+            id: id.to_string(),
+            title: format!("Step {id}"),
+            completed,
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn toggle_checklist_item_auto_completes_parent() {
+        let mut task = Task {
+            completion_rule: "auto".to_string(),
+            checklist: vec![checklist_item("a", true), checklist_item("b", false)],
+            ..Default::default()
+        };
+        let toggle = toggle_checklist_item_in(&mut task, "b").unwrap();
+        assert_eq!(
+            toggle,
+            ChecklistToggle {
+                item_completed: true,
+                parent_completed: Some(true),
+            }
+        );
+        assert!(task.completed);
+        assert!(task.completed_at.is_some());
+
+        let toggle = toggle_checklist_item_in(&mut task, "a").unwrap();
+        assert_eq!(toggle.parent_completed, Some(false));
+        assert!(!task.completed);
+    }
+
+    #[test]
+    fn toggle_checklist_item_manual_rule_leaves_parent() {
+        let mut task = Task {
+            checklist: vec![checklist_item("a", false)],
+            ..Default::default()
+        };
+        let toggle = toggle_checklist_item_in(&mut task, "a").unwrap();
+        assert!(toggle.item_completed);
+        assert_eq!(toggle.parent_completed, None);
+        assert!(!task.completed);
+    }
+
+    #[test]
+    fn toggle_checklist_item_rejects_unknown_item() {
+        let mut task = Task::default();
+        let err = toggle_checklist_item_in(&mut task, "missing").unwrap_err();
+        assert!(err.contains("not found"));
+    }
+
+    #[test]
+    fn reorder_checklist_moves_listed_items_first() {
+        let items = vec![
+            checklist_item("a", false),
+            checklist_item("b", false),
+            checklist_item("c", false),
+        ];
+        let ordered = reorder_checklist(items, &["c".to_string(), "a".to_string()]).unwrap();
+        let ids: Vec<_> = ordered.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, vec!["c", "a", "b"]);
+    }
+
+    #[test]
+    fn reorder_checklist_rejects_unknown_ids() {
+        let err =
+            reorder_checklist(vec![checklist_item("a", false)], &["z".to_string()]).unwrap_err();
+        assert!(err.contains("not found"));
+    }
+
+    #[test]
+    fn backfill_created_at_is_stable_and_keeps_existing_values() {
+        let mut tasks = vec![
+            Task {
+                created_at: "2026-01-01T00:00:00Z".to_string(),
+                ..Default::default()
+            },
+            Task {
+                completed_at: Some("2026-02-03T00:00:00Z".to_string()),
+                archived_at: Some("2026-02-01T00:00:00Z".to_string()),
+                ..Default::default()
+            },
+            Task::default(),
+        ];
+        backfill_created_at(&mut tasks);
+        assert_eq!(tasks[0].created_at, "2026-01-01T00:00:00Z");
+        assert_eq!(tasks[1].created_at, "2026-02-01T00:00:00Z");
+        assert_eq!(tasks[2].created_at, "1970-01-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn subtask_reward_source_id_combines_task_and_item_id() {
+        assert_eq!(subtask_reward_source_id("t1", "i1"), "t1:i1");
+    }
+
+    #[test]
+    fn subtask_rewards_paid_counts_active_payouts_per_task() {
+        let entry = |source_id: &str, revoked: bool| crate::models::RewardLedgerEntry {
+            source_kind: crate::rewards::SOURCE_SUBTASK.to_string(),
+            source_id: source_id.to_string(),
+            reward_kind: crate::rewards::REWARD_XP.to_string(),
+            amount: crate::progression::XP_PER_SUBTASK_COMPLETION,
+            granted_at: "2026-01-01T00:00:00Z".to_string(),
+            revoked_at: revoked.then(|| "2026-01-02T00:00:00Z".to_string()),
+        };
+        let ledger = vec![
+            entry("t1:a", false),
+            entry("t1:b", true),
+            entry("t10:a", false),
+            entry("t1:c", false),
+        ];
+        assert_eq!(subtask_rewards_paid(&ledger, "t1"), 2);
+    }
+
+    #[test]
+    fn completion_rule_follows_added_and_deleted_items() {
+        let mut task = Task {
+            completion_rule: "auto".to_string(),
+            checklist: vec![checklist_item("a", true), checklist_item("b", false)],
+            ..Default::default()
+        };
+        assert_eq!(apply_completion_rule(&mut task), None);
+
+        task.checklist.retain(|item| item.id != "b");
+        assert_eq!(apply_completion_rule(&mut task), Some(true));
+        assert!(task.completed);
+
+        task.checklist.push(checklist_item("c", false));
+        assert_eq!(apply_completion_rule(&mut task), Some(false));
+        assert!(!task.completed);
+
+        task.checklist.clear();
+        assert_eq!(apply_completion_rule(&mut task), None);
+    }
+
+    #[test]
+    fn filter_tasks_by_tag() {
+        let tasks = vec![
+            task_with("1", &["work"], None),
+            task_with("2", &["home"], None),
+        ];
+        let filter = TaskFilter {
+            tag: Some("#Work".to_string()),
+            ..Default::default()
+        };
+        let filtered = filter_tasks(tasks, &filter);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].id, "1");
+    }
+
+    #[test]
+    fn filter_tasks_by_due_range_excludes_undated() {
+        let tasks = vec![
+            task_with("1", &[], Some("2026-03-01")),
+            task_with("2", &[], Some("2026-03-10")),
+            task_with("3", &[], None),
+        ];
+        let filter = TaskFilter {
+            due_to: Some("2026-03-05".to_string()),
+            ..Default::default()
+        };
+        let filtered = filter_tasks(tasks, &filter);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].id, "1");
     }
 }
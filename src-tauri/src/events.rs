@@ -4,6 +4,7 @@ pub const EVENT_GOALS_CHANGED: &str = "goals-changed";
 pub const EVENT_SETTINGS_CHANGED: &str = "settings-changed";
 pub const EVENT_TIMER_RUNTIME_CHANGED: &str = "timer-runtime-changed";
 pub const EVENT_PROFILE_CHANGED: &str = "profile-changed";
+pub const EVENT_LEVEL_UP: &str = "level-up";
 pub const EVENT_ANALYTICS_CHANGED: &str = "analytics-changed";
 pub const EVENT_FOCUS_GUARDRAILS_ALERT: &str = "focus-guardrails-alert";
 
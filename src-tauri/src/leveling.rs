@@ -0,0 +1,289 @@
+use serde_json::json;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_store::StoreExt;
+
+use crate::events::{EVENT_LEVEL_UP, EVENT_PET_STATE_CHANGED};
+use crate::models::{LevelReward, LevelUpEvent, PetState, UserProgress};
+
+pub const ALLOWED_XP_CURVES: &[&str] = &["linear", "quadratic", "table"];
+const MAX_LEVEL: u32 = 500;
+const LINEAR_XP_PER_LEVEL: u32 = 100;
+/// Reaching level `n + 1` from level `n` costs `n` times this much XP.
+const QUADRATIC_XP_STEP: u32 = 100;
+/// Total XP needed for levels 1, 2, 3, ...; later levels repeat the last gap.
+const LEVEL_XP_TABLE: &[u32] = &[0, 100, 250, 450, 700, 1000, 1400, 1900, 2500, 3200, 4000];
+
+/// Total XP needed to reach `level` on `curve`. Unknown curves use the quadratic one.
+pub fn level_threshold(curve: &str, level: u32) -> u32 {
+    let steps = level.saturating_sub(1);
+    match curve {
+        "linear" => steps.saturating_mul(LINEAR_XP_PER_LEVEL),
+        "table" => {
+            let index = steps as usize;
+            let last = LEVEL_XP_TABLE.len() - 1;
+            if index <= last {
+                LEVEL_XP_TABLE[index]
+            } else {
+                let gap = LEVEL_XP_TABLE[last] - LEVEL_XP_TABLE[last - 1];
+                LEVEL_XP_TABLE[last].saturating_add(gap.saturating_mul((index - last) as u32))
+            }
+        }
+        _ => QUADRATIC_XP_STEP.saturating_mul(steps.saturating_mul(steps + 1) / 2),
+    }
+}
+
+pub fn xp_to_level(curve: &str, xp_total: u32) -> u32 {
+    let mut level = 1;
+    while level < MAX_LEVEL && level_threshold(curve, level + 1) <= xp_total {
+        level += 1;
+    }
+    level
+}
+
+/// Sets `level` and `xp_to_next_level` from the XP total.
+pub fn apply_level(progress: &mut UserProgress, curve: &str) {
+    progress.level = xp_to_level(curve, progress.xp_total);
+    progress.xp_to_next_level = if progress.level >= MAX_LEVEL {
+        0
+    } else {
+        level_threshold(curve, progress.level + 1).saturating_sub(progress.xp_total)
+    };
+}
+
+fn reward(level: u32, coins: u32, skins: &[&str], scenes: &[&str], items: &[&str]) -> LevelReward {
+    let owned = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect();
+    LevelReward {
+        level,
+        coins,
+        skins: owned(skins),
+        scenes: owned(scenes),
+        items: owned(items),
+    }
+}
+
+/// Rewards granted once when each level is first reached.
+pub fn level_rewards() -> Vec<LevelReward> {
+    vec![
+        reward(2, 20, &[], &[], &[]),
+        reward(3, 30, &["neon"], &[], &[]),
+        reward(5, 50, &[], &["space"], &["party_hat"]),
+        reward(7, 70, &["pixel"], &[], &[]),
+        reward(10, 100, &["plush"], &["cozy_room"], &[]),
+    ]
+}
+
+fn cosmetic_key(kind: &str, id: &str) -> String {
+    format!("{}:{}", kind, id)
+}
+
+/// Level that unlocks a skin or scene, or `None` when it is available from the start.
+fn unlock_level(kind: &str, id: &str) -> Option<u32> {
+    level_rewards()
+        .into_iter()
+        .find(|reward| {
+            let ids = if kind == "skin" {
+                &reward.skins
+            } else {
+                &reward.scenes
+            };
+            ids.iter().any(|candidate| candidate == id)
+        })
+        .map(|reward| reward.level)
+}
+
+fn check_cosmetic_unlocked(
+    progress: &UserProgress,
+    kind: &str,
+    id: &str,
+    current: &str,
+) -> Result<(), String> {
+    if id == current
+        || progress
+            .unlocked_cosmetics
+            .contains(&cosmetic_key(kind, id))
+    {
+        return Ok(());
+    }
+    match unlock_level(kind, id) {
+        Some(level) => Err(format!("The {} {} unlocks at level {}", id, kind, level)),
+        None => Ok(()),
+    }
+}
+
+/// Rejects level-locked skins and scenes; the one currently in use always stays allowed.
+pub fn ensure_cosmetic_unlocked(
+    app: &AppHandle,
+    kind: &str,
+    id: &str,
+    current: &str,
+) -> Result<(), String> {
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    let progress: UserProgress = store
+        .get("user_progress")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    check_cosmetic_unlocked(&progress, kind, id, current)
+}
+
+fn unlock_cosmetics(progress: &mut UserProgress, reward: &LevelReward) {
+    let keys = reward
+        .skins
+        .iter()
+        .map(|id| cosmetic_key("skin", id))
+        .chain(reward.scenes.iter().map(|id| cosmetic_key("scene", id)));
+    for key in keys {
+        if !progress.unlocked_cosmetics.contains(&key) {
+            progress.unlocked_cosmetics.push(key);
+        }
+    }
+}
+
+/// Claims rewards for every level reached so far and records their unlocks on `progress`.
+/// Coins and items are paid by `announce_level_up` once the progress has been saved.
+pub fn claim_level_rewards(
+    app: &AppHandle,
+    progress: &mut UserProgress,
+) -> Result<Vec<LevelReward>, String> {
+    let reached: Vec<LevelReward> = level_rewards()
+        .into_iter()
+        .filter(|reward| reward.level <= progress.level)
+        .collect();
+    let claims: Vec<(String, u32)> = reached
+        .iter()
+        .map(|reward| (reward.level.to_string(), reward.coins))
+        .collect();
+    let claimed = crate::rewards::claim_many(
+        app,
+        crate::rewards::SOURCE_LEVEL,
+        crate::rewards::REWARD_COINS,
+        &claims,
+    )?;
+    let mut granted = Vec::new();
+    for (reward, claimed) in reached.into_iter().zip(claimed) {
+        if claimed {
+            unlock_cosmetics(progress, &reward);
+            granted.push(reward);
+        }
+    }
+    Ok(granted)
+}
+
+fn grant_items(app: &AppHandle, items: &[String]) -> Result<(), String> {
+    if items.is_empty() {
+        return Ok(());
+    }
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    let mut pet: PetState = store
+        .get("pet")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    crate::commands::shop::normalize_pet_items(&mut pet);
+    for item in items {
+        crate::commands::shop::give_item(&mut pet, item);
+    }
+    store.set("pet", json!(pet));
+    let _ = app.emit(EVENT_PET_STATE_CHANGED, &pet);
+    Ok(())
+}
+
+/// Pays out claimed level rewards and emits `EVENT_LEVEL_UP` when the level rose.
+pub fn announce_level_up(
+    app: &AppHandle,
+    previous_level: u32,
+    level: u32,
+    rewards: Vec<LevelReward>,
+) -> Result<(), String> {
+    for reward in rewards.iter().filter(|reward| reward.coins > 0) {
+        crate::commands::coins::credit_coins(
+            app,
+            reward.coins,
+            crate::commands::coins::COIN_SOURCE_LEVEL,
+            Some(&reward.level.to_string()),
+        )?;
+        crate::progression::record_coins_earned(app, reward.coins)?;
+    }
+    let items: Vec<String> = rewards
+        .iter()
+        .flat_map(|reward| reward.items.iter().cloned())
+        .collect();
+    grant_items(app, &items)?;
+
+    if level > previous_level || !rewards.is_empty() {
+        let _ = app.emit(
+            EVENT_LEVEL_UP,
+            &LevelUpEvent {
+                previous_level,
+                level,
+                rewards,
+            },
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_curve_matches_legacy_levels() {
+        assert_eq!(xp_to_level("linear", 0), 1);
+        assert_eq!(xp_to_level("linear", 99), 1);
+        assert_eq!(xp_to_level("linear", 100), 2);
+        assert_eq!(xp_to_level("linear", 550), 6);
+    }
+
+    #[test]
+    fn quadratic_curve_grows_level_gaps() {
+        assert_eq!(level_threshold("quadratic", 2), 100);
+        assert_eq!(level_threshold("quadratic", 3), 300);
+        assert_eq!(level_threshold("quadratic", 4), 600);
+        assert_eq!(xp_to_level("quadratic", 599), 3);
+        assert_eq!(xp_to_level("quadratic", 600), 4);
+    }
+
+    #[test]
+    fn table_curve_extends_past_last_entry() {
+        assert_eq!(level_threshold("table", 1), 0);
+        assert_eq!(level_threshold("table", 11), 4000);
+        assert_eq!(level_threshold("table", 12), 4800);
+        assert_eq!(xp_to_level("table", 4799), 11);
+    }
+
+    #[test]
+    fn apply_level_sets_xp_to_next_level() {
+        let mut progress = UserProgress {
+            xp_total: 250,
+            ..Default::default()
+        };
+        apply_level(&mut progress, "quadratic");
+        assert_eq!(progress.level, 2);
+        assert_eq!(progress.xp_to_next_level, 50);
+
+        progress.xp_total = u32::MAX;
+        apply_level(&mut progress, "linear");
+        assert_eq!(progress.level, MAX_LEVEL);
+        assert_eq!(progress.xp_to_next_level, 0);
+    }
+
+    #[test]
+    fn level_rewards_are_ordered_and_unique() {
+        let rewards = level_rewards();
+        assert!(rewards.windows(2).all(|pair| pair[0].level < pair[1].level));
+        assert!(rewards.iter().all(|reward| reward.level > 1));
+    }
+
+    #[test]
+    fn locked_cosmetics_need_their_level_reward() {
+        let mut progress = UserProgress::default();
+        assert!(check_cosmetic_unlocked(&progress, "skin", "classic", "classic").is_ok());
+        assert!(check_cosmetic_unlocked(&progress, "scene", "forest", "meadow").is_ok());
+        let err = check_cosmetic_unlocked(&progress, "skin", "neon", "classic").unwrap_err();
+        assert!(err.contains("level 3"));
+        assert!(check_cosmetic_unlocked(&progress, "skin", "neon", "neon").is_ok());
+
+        unlock_cosmetics(&mut progress, &level_rewards()[1]);
+        assert!(check_cosmetic_unlocked(&progress, "skin", "neon", "classic").is_ok());
+        assert_eq!(progress.unlocked_cosmetics, vec!["skin:neon".to_string()]);
+    }
+}
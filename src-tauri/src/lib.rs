@@ -1,8 +1,12 @@
 mod achievements;
+mod clock;
 mod commands;
 mod events;
+mod leveling;
 mod models;
+mod progress_rebuild;
 mod progression;
+mod rewards;
 mod storage;
 
 use crate::events::{
@@ -32,45 +36,83 @@ pub fn run() {
             commands::pet::get_pet_state,
             commands::pet::set_pet_animation,
             commands::pet::pet_interact,
+            commands::pet::feed_item,
             commands::pet::set_pet_customization,
             commands::pet::set_pet_species,
             commands::pet::get_pet_events,
             commands::pet::get_pet_active_quest,
+            commands::pet::reroll_quest,
             commands::pet::resolve_pet_event,
             commands::pet::roll_pet_event,
             commands::pomodoro::start_pomodoro,
             commands::pomodoro::complete_pomodoro,
+            commands::pomodoro::complete_break,
             commands::timer_runtime::get_timer_runtime,
             commands::timer_runtime::save_timer_runtime,
             commands::timer_runtime::clear_timer_runtime,
             commands::progress::get_user_progress,
+            commands::progress::buy_streak_freeze,
             commands::progress::get_daily_summaries,
+            commands::analytics::get_analytics_report,
             commands::focus_guardrails::evaluate_focus_guardrails,
             commands::focus_guardrails::apply_focus_guardrails_intervention,
             commands::focus_guardrails::get_focus_guardrail_events,
             commands::customization::get_customization_loadouts,
             commands::customization::save_customization_loadout,
             commands::customization::apply_customization_loadout,
+            commands::customization::equip_accessory,
+            commands::customization::unequip_accessory,
             commands::settings::get_settings,
             commands::settings::update_settings,
             commands::coins::get_coin_balance,
-            commands::coins::spend_coins,
+            commands::coins::get_coin_transactions,
+            commands::coins::check_coin_integrity,
             commands::tasks::get_tasks,
             commands::tasks::add_task,
+            commands::tasks::update_task,
             commands::tasks::toggle_task,
             commands::tasks::delete_task,
+            commands::tasks::add_checklist_item,
+            commands::tasks::toggle_checklist_item,
+            commands::tasks::reorder_checklist_items,
+            commands::tasks::delete_checklist_item,
+            commands::tasks::get_task_lists,
+            commands::tasks::create_task_list,
+            commands::tasks::rename_task_list,
+            commands::tasks::delete_task_list,
+            commands::tasks::move_task_to_list,
+            commands::tasks::reorder_tasks,
+            commands::tasks::archive_task,
+            commands::tasks::unarchive_task,
+            commands::tasks::restore_task,
+            commands::tasks::empty_trash,
+            commands::task_transfer::import_tasks,
+            commands::task_transfer::export_tasks,
             commands::goals::get_daily_goals,
             commands::goals::update_goal_progress,
+            commands::goals::get_goal_history,
+            commands::goals::get_goal_templates,
+            commands::goals::create_goal_template,
+            commands::goals::update_goal_template,
+            commands::goals::delete_goal_template,
+            commands::period_goals::get_period_goals,
+            commands::period_goals::get_period_goal_templates,
+            commands::period_goals::create_period_goal_template,
+            commands::period_goals::update_period_goal_template,
+            commands::period_goals::delete_period_goal_template,
             commands::shop::get_shop_items,
             commands::shop::purchase_item,
+            commands::shop::refund_purchase,
             commands::maintenance::export_app_snapshot,
             commands::maintenance::import_app_snapshot,
             commands::maintenance::reset_app_state,
             commands::maintenance::get_app_diagnostics,
+            commands::maintenance::rebuild_progress,
             commands::tray::set_tray_badge,
         ])
         .setup(|app| {
             storage::ensure_schema_version(app.handle()).map_err(std::io::Error::other)?;
+            commands::shop::validate_bundled_catalog().map_err(std::io::Error::other)?;
 
             // Build system tray
             let show_pet = MenuItem::with_id(app, "show_pet", "Show Pet", true, None::<&str>)?;
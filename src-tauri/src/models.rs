@@ -6,13 +6,25 @@ fn default_quest_kind() -> String {
     "focus_sessions".to_string()
 }
 
+/// Curve for settings saved before the XP curve was configurable, so levels don't shift.
+pub const LEGACY_XP_CURVE: &str = "linear";
+
+fn legacy_xp_curve() -> String {
+    LEGACY_XP_CURVE.to_string()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 #[serde(rename_all = "camelCase")]
 pub struct PetState {
     pub current_stage: u32,
     pub animation_state: String,
+    /// Accessories the pet is wearing in slot order, mirroring `equipped`.
     pub accessories: Vec<String>,
+    /// Accessories bought in the shop.
+    pub owned_accessories: Vec<String>,
+    /// Owned accessory worn in each slot (`head`, `face`, `neck`, `held`).
+    pub equipped: std::collections::BTreeMap<String, String>,
     pub total_pomodoros: u32,
     pub species_id: String,
     pub evolution_thresholds: Vec<u32>,
@@ -27,6 +39,10 @@ pub struct PetState {
     pub scene: String,
     pub last_interaction: Option<String>,
     pub last_care_update_at: String,
+    /// Units of consumable food items by shop item id.
+    pub inventory: std::collections::BTreeMap<String, u32>,
+    /// One-time item migrations already applied to this pet.
+    pub items_version: u32,
 }
 
 impl Default for PetState {
@@ -35,6 +51,8 @@ impl Default for PetState {
             current_stage: 0,
             animation_state: "idle".to_string(),
             accessories: vec![],
+            owned_accessories: vec![],
+            equipped: std::collections::BTreeMap::new(),
             total_pomodoros: 0,
             species_id: "penguin".to_string(),
             evolution_thresholds: vec![0, 5, 15],
@@ -49,6 +67,8 @@ impl Default for PetState {
             scene: "meadow".to_string(),
             last_interaction: None,
             last_care_update_at: chrono::Utc::now().to_rfc3339(),
+            inventory: std::collections::BTreeMap::new(),
+            items_version: 0,
         }
     }
 }
@@ -85,6 +105,9 @@ pub struct PomodoroSession {
     pub completed_at: Option<String>,
     pub work_duration: u32,
     pub break_duration: u32,
+    /// Set when the break after this session was taken rather than skipped.
+    #[serde(default)]
+    pub break_completed_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -100,6 +123,28 @@ impl Default for CoinBalance {
     }
 }
 
+/// One append-only movement of coins; `direction` is `credit` or `debit`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinTransaction {
+    pub id: String,
+    pub amount: u32,
+    pub direction: String,
+    pub source: String,
+    pub reference_id: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinIntegrityReport {
+    pub balance_total: u32,
+    pub balance_spent: u32,
+    pub ledger_credits: u64,
+    pub ledger_debits: u64,
+    pub consistent: bool,
+}
+
 impl CoinBalance {
     pub fn available(&self) -> u32 {
         self.total.saturating_sub(self.spent)
@@ -107,12 +152,193 @@ impl CoinBalance {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 #[serde(rename_all = "camelCase")]
 pub struct Task {
     pub id: String,
     pub title: String,
     pub completed: bool,
     pub created_at: String,
+    pub priority: String,
+    pub due_date: Option<String>,
+    pub notes: String,
+    pub tags: Vec<String>,
+    pub completed_at: Option<String>,
+    pub checklist: Vec<ChecklistItem>,
+    pub completion_rule: String,
+    pub recurrence: Option<TaskRecurrence>,
+    pub archived_at: Option<String>,
+    /// Set when the task is moved to the trash; purged after the retention period.
+    pub deleted_at: Option<String>,
+    /// Shared by every instance generated from the same recurring task.
+    pub series_id: Option<String>,
+    pub list_id: String,
+    /// Position within the task's list; ties keep insertion order.
+    pub sort_index: u32,
+}
+
+impl Default for Task {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            title: String::new(),
+            completed: false,
+            created_at: String::new(),
+            priority: "none".to_string(),
+            due_date: None,
+            notes: String::new(),
+            tags: vec![],
+            completed_at: None,
+            checklist: vec![],
+            completion_rule: "manual".to_string(),
+            recurrence: None,
+            archived_at: None,
+            deleted_at: None,
+            series_id: None,
+            list_id: INBOX_TASK_LIST_ID.to_string(),
+            sort_index: 0,
+        }
+    }
+}
+
+pub const INBOX_TASK_LIST_ID: &str = "inbox";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskList {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
+}
+
+impl TaskList {
+    pub fn inbox() -> Self {
+        Self {
+            id: INBOX_TASK_LIST_ID.to_string(),
+            name: "Inbox".to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// How often a recurring task repeats: "daily", "weekdays", "weekly" (on `weekdays`,
+/// 0 = Monday) or "interval" (every `interval_days` days).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskRecurrence {
+    pub frequency: String,
+    pub weekdays: Vec<u8>,
+    pub interval_days: u32,
+}
+
+impl Default for TaskRecurrence {
+    fn default() -> Self {
+        Self {
+            frequency: "daily".to_string(),
+            weekdays: vec![],
+            interval_days: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistItem {
+    pub id: String,
+    pub title: String,
+    pub completed: bool,
+    pub completed_at: Option<String>,
+}
+
+/// Partial task update. An empty `due_date` clears the due date and a recurrence
+/// with frequency "none" stops the task from repeating.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskPatch {
+    pub title: Option<String>,
+    pub priority: Option<String>,
+    pub due_date: Option<String>,
+    pub notes: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub completion_rule: Option<String>,
+    pub recurrence: Option<TaskRecurrence>,
+}
+
+impl TaskPatch {
+    pub fn apply_to(self, task: &mut Task) {
+        if let Some(title) = self.title {
+            task.title = title;
+        }
+        if let Some(priority) = self.priority {
+            task.priority = priority;
+        }
+        if let Some(due_date) = self.due_date {
+            task.due_date = if due_date.is_empty() {
+                None
+            } else {
+                Some(due_date)
+            };
+        }
+        if let Some(notes) = self.notes {
+            task.notes = notes;
+        }
+        if let Some(tags) = self.tags {
+            task.tags = tags;
+        }
+        if let Some(completion_rule) = self.completion_rule {
+            task.completion_rule = completion_rule;
+        }
+        if let Some(recurrence) = self.recurrence {
+            if recurrence.frequency == "none" {
+                task.recurrence = None;
+            } else {
+                task.series_id.get_or_insert_with(|| task.id.clone());
+                task.recurrence = Some(recurrence);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskFilter {
+    /// "active" (default), "archived" or "trash".
+    pub view: Option<String>,
+    pub list_id: Option<String>,
+    pub tag: Option<String>,
+    pub due_from: Option<String>,
+    pub due_to: Option<String>,
+}
+
+/// Outcome of one non-blank input line: "imported", "duplicate", "rejected" or "skipped".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskImportLine {
+    pub line: u32,
+    pub status: String,
+    pub title: String,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskImportReport {
+    pub imported: u32,
+    pub duplicates: u32,
+    pub rejected: u32,
+    pub lines: Vec<TaskImportLine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardLedgerEntry {
+    pub source_kind: String,
+    pub source_id: String,
+    pub reward_kind: String,
+    pub amount: u32,
+    pub granted_at: String,
+    pub revoked_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -123,6 +349,120 @@ pub struct DailyGoal {
     pub target: u32,
     pub progress: u32,
     pub date: String,
+    /// What the goal counts. Goals saved before templates existed use their id.
+    #[serde(default)]
+    pub metric: String,
+    /// Why an adaptive target differs from the template, shown in the goals panel.
+    #[serde(default)]
+    pub target_reason: Option<String>,
+}
+
+/// Blueprint for a weekly or monthly goal.
+///
+/// `day_threshold_minutes` only applies to the `focus_days` metric, which counts days
+/// with at least that much focus.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodGoalTemplate {
+    pub id: String,
+    pub period: String,
+    pub metric: String,
+    pub target: u32,
+    #[serde(default)]
+    pub day_threshold_minutes: u32,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodGoalTemplatePatch {
+    pub target: Option<u32>,
+    pub day_threshold_minutes: Option<u32>,
+    pub enabled: Option<bool>,
+}
+
+impl PeriodGoalTemplatePatch {
+    pub fn apply_to(self, template: &mut PeriodGoalTemplate) {
+        if let Some(target) = self.target {
+            template.target = target;
+        }
+        if let Some(minutes) = self.day_threshold_minutes {
+            template.day_threshold_minutes = minutes;
+        }
+        if let Some(enabled) = self.enabled {
+            template.enabled = enabled;
+        }
+    }
+}
+
+/// A weekly or monthly goal for the current period; `period_end` is inclusive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodGoal {
+    pub id: String,
+    pub period: String,
+    pub metric: String,
+    pub description: String,
+    pub target: u32,
+    pub progress: u32,
+    pub period_start: String,
+    pub period_end: String,
+    pub completed_at: Option<String>,
+}
+
+/// Final state of one goal, recorded when its day rolls over.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalOutcome {
+    pub id: String,
+    pub metric: String,
+    pub target: u32,
+    pub progress: u32,
+    pub completed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalHistoryDay {
+    pub date: String,
+    pub goals: Vec<GoalOutcome>,
+}
+
+/// Blueprint for a daily goal. `weekdays` uses 0 = Monday; empty means every day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalTemplate {
+    pub id: String,
+    pub metric: String,
+    pub target: u32,
+    pub enabled: bool,
+    pub weekdays: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalTemplatePatch {
+    pub metric: Option<String>,
+    pub target: Option<u32>,
+    pub enabled: Option<bool>,
+    pub weekdays: Option<Vec<u8>>,
+}
+
+impl GoalTemplatePatch {
+    pub fn apply_to(self, template: &mut GoalTemplate) {
+        if let Some(metric) = self.metric {
+            template.metric = metric;
+        }
+        if let Some(target) = self.target {
+            template.target = target;
+        }
+        if let Some(enabled) = self.enabled {
+            template.enabled = enabled;
+        }
+        if let Some(weekdays) = self.weekdays {
+            template.weekdays = weekdays;
+        }
+    }
 }
 
 #[allow(dead_code)]
@@ -155,6 +495,30 @@ pub struct Settings {
     pub focus_guardrails_work_only: bool,
     pub focus_allowlist: Vec<String>,
     pub focus_blocklist: Vec<String>,
+    pub task_reward_clawback_enabled: bool,
+    /// Completed tasks are archived this many days after completion; 0 disables it.
+    pub task_auto_archive_days: u32,
+    /// Trashed tasks are purged this many days after deletion; 0 keeps them forever.
+    pub task_trash_retention_days: u32,
+    /// Derive daily goal targets from recent summaries instead of the fixed templates.
+    pub adaptive_goals_enabled: bool,
+    pub adaptive_goal_stretch_percent: u32,
+    /// First day of the week for weekly goals, 0 = Monday.
+    pub week_start_day: u8,
+    /// Coins paid for each completed daily goal.
+    pub goal_reward_coins: u32,
+    /// Extra coins and pet affection when every daily goal is complete.
+    pub perfect_day_bonus_coins: u32,
+    pub perfect_day_affection_boost: u32,
+    /// IANA zone used for day boundaries; empty uses the system time zone.
+    pub time_zone: String,
+    /// Hour at which a new day starts, so late-night work counts towards the previous day.
+    pub day_start_hour: u8,
+    /// Level curve: "linear", "quadratic" or "table". Fresh installs start on "quadratic".
+    #[serde(default = "legacy_xp_curve")]
+    pub xp_curve: String,
+    /// Missed Saturdays and Sundays do not break the streak.
+    pub streak_weekend_grace: bool,
 }
 
 impl Default for Settings {
@@ -192,6 +556,19 @@ impl Default for Settings {
             focus_guardrails_work_only: true,
             focus_allowlist: vec![],
             focus_blocklist: vec![],
+            task_reward_clawback_enabled: false,
+            task_auto_archive_days: 7,
+            task_trash_retention_days: 30,
+            adaptive_goals_enabled: false,
+            adaptive_goal_stretch_percent: 10,
+            week_start_day: 0,
+            goal_reward_coins: 5,
+            perfect_day_bonus_coins: 25,
+            perfect_day_affection_boost: 10,
+            time_zone: String::new(),
+            day_start_hour: 0,
+            xp_curve: "quadratic".to_string(),
+            streak_weekend_grace: false,
         }
     }
 }
@@ -224,6 +601,19 @@ pub struct SettingsPatch {
     pub focus_guardrails_work_only: Option<bool>,
     pub focus_allowlist: Option<Vec<String>>,
     pub focus_blocklist: Option<Vec<String>>,
+    pub task_reward_clawback_enabled: Option<bool>,
+    pub task_auto_archive_days: Option<u32>,
+    pub task_trash_retention_days: Option<u32>,
+    pub adaptive_goals_enabled: Option<bool>,
+    pub adaptive_goal_stretch_percent: Option<u32>,
+    pub week_start_day: Option<u8>,
+    pub goal_reward_coins: Option<u32>,
+    pub perfect_day_bonus_coins: Option<u32>,
+    pub perfect_day_affection_boost: Option<u32>,
+    pub time_zone: Option<String>,
+    pub day_start_hour: Option<u8>,
+    pub xp_curve: Option<String>,
+    pub streak_weekend_grace: Option<bool>,
 }
 
 impl SettingsPatch {
@@ -303,6 +693,45 @@ impl SettingsPatch {
         if let Some(blocklist) = self.focus_blocklist {
             settings.focus_blocklist = blocklist;
         }
+        if let Some(enabled) = self.task_reward_clawback_enabled {
+            settings.task_reward_clawback_enabled = enabled;
+        }
+        if let Some(days) = self.task_auto_archive_days {
+            settings.task_auto_archive_days = days;
+        }
+        if let Some(days) = self.task_trash_retention_days {
+            settings.task_trash_retention_days = days;
+        }
+        if let Some(enabled) = self.adaptive_goals_enabled {
+            settings.adaptive_goals_enabled = enabled;
+        }
+        if let Some(percent) = self.adaptive_goal_stretch_percent {
+            settings.adaptive_goal_stretch_percent = percent;
+        }
+        if let Some(day) = self.week_start_day {
+            settings.week_start_day = day;
+        }
+        if let Some(coins) = self.goal_reward_coins {
+            settings.goal_reward_coins = coins;
+        }
+        if let Some(coins) = self.perfect_day_bonus_coins {
+            settings.perfect_day_bonus_coins = coins;
+        }
+        if let Some(boost) = self.perfect_day_affection_boost {
+            settings.perfect_day_affection_boost = boost;
+        }
+        if let Some(time_zone) = self.time_zone {
+            settings.time_zone = time_zone;
+        }
+        if let Some(hour) = self.day_start_hour {
+            settings.day_start_hour = hour;
+        }
+        if let Some(curve) = self.xp_curve {
+            settings.xp_curve = curve;
+        }
+        if let Some(enabled) = self.streak_weekend_grace {
+            settings.streak_weekend_grace = enabled;
+        }
     }
 }
 
@@ -345,6 +774,23 @@ pub struct UserProgress {
     pub total_sessions: u32,
     pub total_focus_minutes: u32,
     pub total_tasks_completed: u32,
+    #[serde(default)]
+    pub xp_to_next_level: u32,
+    /// Level-locked skins and scenes already unlocked, as `skin:<id>` / `scene:<id>`.
+    #[serde(default)]
+    pub unlocked_cosmetics: Vec<String>,
+    #[serde(default)]
+    pub streak_freezes: u32,
+    /// Missed days covered by a freeze, oldest first.
+    #[serde(default)]
+    pub streak_freeze_log: Vec<StreakFreezeUse>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreakFreezeUse {
+    pub date: String,
+    pub used_at: String,
 }
 
 impl Default for UserProgress {
@@ -358,10 +804,33 @@ impl Default for UserProgress {
             total_sessions: 0,
             total_focus_minutes: 0,
             total_tasks_completed: 0,
+            xp_to_next_level: 100,
+            unlocked_cosmetics: vec![],
+            streak_freezes: 0,
+            streak_freeze_log: vec![],
         }
     }
 }
 
+/// What reaching a level grants: coins, level-locked skins and scenes, and shop items.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LevelReward {
+    pub level: u32,
+    pub coins: u32,
+    pub skins: Vec<String>,
+    pub scenes: Vec<String>,
+    pub items: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LevelUpEvent {
+    pub previous_level: u32,
+    pub level: u32,
+    pub rewards: Vec<LevelReward>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 #[serde(rename_all = "camelCase")]
@@ -375,6 +844,7 @@ pub struct DailySummary {
     pub xp_earned: u32,
     pub guardrails_interventions: u32,
     pub high_nudges: u32,
+    pub breaks_taken: u32,
 }
 
 impl Default for DailySummary {
@@ -389,6 +859,7 @@ impl Default for DailySummary {
             xp_earned: 0,
             guardrails_interventions: 0,
             high_nudges: 0,
+            breaks_taken: 0,
         }
     }
 }
@@ -400,7 +871,12 @@ pub struct CustomizationLoadout {
     pub ui_theme: String,
     pub pet_skin: String,
     pub pet_scene: String,
+    /// Flat accessory list from before slots; folded into `equipped` when sanitized.
+    #[serde(default)]
     pub accessories: Vec<String>,
+    /// Accessory per slot.
+    #[serde(default)]
+    pub equipped: std::collections::BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -437,8 +913,17 @@ pub struct AppSnapshot {
     pub exported_at: String,
     pub pet: PetState,
     pub coins: CoinBalance,
+    /// `None` seeds a fresh ledger from `coins` on first use.
+    pub coin_transactions: Option<Vec<CoinTransaction>>,
     pub tasks: Vec<Task>,
+    pub task_lists: Vec<TaskList>,
     pub goals: Vec<DailyGoal>,
+    /// `None` keeps the built-in goal templates.
+    pub goal_templates: Option<Vec<GoalTemplate>>,
+    pub goal_history: Vec<GoalHistoryDay>,
+    /// `None` keeps the built-in weekly and monthly goal templates.
+    pub period_goal_templates: Option<Vec<PeriodGoalTemplate>>,
+    pub summary_rollups: Vec<SummaryRollup>,
     pub sessions: Vec<PomodoroSession>,
     pub settings: Settings,
     pub timer_runtime: TimerRuntimeState,
@@ -448,6 +933,7 @@ pub struct AppSnapshot {
     pub pet_events: Vec<PetEvent>,
     pub pet_active_quest: Option<PetQuest>,
     pub focus_guardrail_events: Vec<FocusGuardrailEvent>,
+    pub reward_ledger: Vec<RewardLedgerEntry>,
 }
 
 impl Default for AppSnapshot {
@@ -457,8 +943,14 @@ impl Default for AppSnapshot {
             exported_at: chrono::Utc::now().to_rfc3339(),
             pet: PetState::default(),
             coins: CoinBalance::default(),
+            coin_transactions: None,
             tasks: vec![],
+            task_lists: vec![TaskList::inbox()],
             goals: vec![],
+            goal_templates: None,
+            goal_history: vec![],
+            period_goal_templates: None,
+            summary_rollups: vec![],
             sessions: vec![],
             settings: Settings::default(),
             timer_runtime: TimerRuntimeState::default(),
@@ -468,10 +960,62 @@ impl Default for AppSnapshot {
             pet_events: vec![],
             pet_active_quest: None,
             focus_guardrail_events: vec![],
+            reward_ledger: vec![],
         }
     }
 }
 
+/// Daily summaries added up over one week or month.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SummaryRollup {
+    /// `week` or `month`.
+    pub period: String,
+    pub start: String,
+    pub end: String,
+    pub active_days: u32,
+    pub sessions_completed: u32,
+    pub focus_minutes: u32,
+    pub tasks_completed: u32,
+    pub goals_completed: u32,
+    pub coins_earned: u32,
+    pub xp_earned: u32,
+    pub breaks_taken: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyticsReport {
+    /// Newest first, including weeks whose daily summaries have aged out.
+    pub weekly: Vec<SummaryRollup>,
+    pub monthly: Vec<SummaryRollup>,
+    /// Focus minutes by weekday (0 = Monday) and local start hour.
+    pub focus_heatmap: Vec<Vec<u32>>,
+    pub best_weekday: Option<u8>,
+    pub worst_weekday: Option<u8>,
+    pub average_session_minutes: f32,
+    /// Share of started sessions that were completed, from 0 to 1.
+    pub completion_rate: f32,
+}
+
+/// One counter that differs between the stored and the rebuilt progress.
+/// `scope` is `progress` for lifetime totals or the `YYYY-MM-DD` date of a daily summary.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgressChange {
+    pub scope: String,
+    pub field: String,
+    pub before: u32,
+    pub after: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgressRebuildReport {
+    pub applied: bool,
+    pub changes: Vec<ProgressChange>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AppDiagnostics {
@@ -640,6 +1184,17 @@ mod tests {
             current_stage: 1,
             animation_state: "working".to_string(),
             accessories: vec!["party_hat".to_string(), "bow_tie".to_string()],
+            owned_accessories: vec![
+                "party_hat".to_string(),
+                "bow_tie".to_string(),
+                "scarf".to_string(),
+            ],
+            equipped: [
+                ("head".to_string(), "party_hat".to_string()),
+                ("neck".to_string(), "bow_tie".to_string()),
+            ]
+            .into_iter()
+            .collect(),
             total_pomodoros: 7,
             species_id: "penguin".to_string(),
             evolution_thresholds: vec![0, 5, 15],
@@ -654,6 +1209,8 @@ mod tests {
             scene: "meadow".to_string(),
             last_interaction: Some("pet".to_string()),
             last_care_update_at: "2025-01-01T00:00:00Z".to_string(),
+            inventory: Default::default(),
+            items_version: 1,
         };
         let json = serde_json::to_value(&p).unwrap();
         let acc = json.get("accessories").unwrap().as_array().unwrap();
@@ -668,6 +1225,10 @@ mod tests {
             current_stage: 2,
             animation_state: "celebrating".to_string(),
             accessories: vec!["sunglasses".to_string()],
+            owned_accessories: vec!["sunglasses".to_string(), "scarf".to_string()],
+            equipped: [("face".to_string(), "sunglasses".to_string())]
+                .into_iter()
+                .collect(),
             total_pomodoros: 20,
             species_id: "penguin".to_string(),
             evolution_thresholds: vec![0, 5, 15],
@@ -682,15 +1243,24 @@ mod tests {
             scene: "space".to_string(),
             last_interaction: Some("train".to_string()),
             last_care_update_at: "2025-06-01T00:00:00Z".to_string(),
+            inventory: [("cookie".to_string(), 3)].into_iter().collect(),
+            items_version: 1,
         };
         let json_str = serde_json::to_string(&original).unwrap();
         let restored: PetState = serde_json::from_str(&json_str).unwrap();
         assert_eq!(restored.current_stage, 2);
         assert_eq!(restored.animation_state, "celebrating");
         assert_eq!(restored.accessories, vec!["sunglasses"]);
+        assert_eq!(restored.owned_accessories, vec!["sunglasses", "scarf"]);
+        assert_eq!(
+            restored.equipped.get("face").map(String::as_str),
+            Some("sunglasses")
+        );
         assert_eq!(restored.total_pomodoros, 20);
         assert_eq!(restored.scene, "space");
         assert_eq!(restored.evolution_path, "scholar");
+        assert_eq!(restored.inventory.get("cookie"), Some(&3));
+        assert_eq!(restored.items_version, 1);
     }
 
     #[test]
@@ -721,6 +1291,7 @@ mod tests {
             completed_at: None,
             work_duration: 1500,
             break_duration: 300,
+            break_completed_at: None,
         };
         let json = serde_json::to_value(&s).unwrap();
         assert!(json.get("startedAt").is_some());
@@ -739,6 +1310,7 @@ mod tests {
             completed_at: None,
             work_duration: 1500,
             break_duration: 300,
+            break_completed_at: None,
         };
         let json = serde_json::to_value(&s).unwrap();
         assert!(json.get("completedAt").unwrap().is_null());
@@ -752,6 +1324,7 @@ mod tests {
             completed_at: Some("2025-01-01T00:25:00Z".to_string()),
             work_duration: 1500,
             break_duration: 300,
+            break_completed_at: None,
         };
         let json = serde_json::to_value(&s).unwrap();
         assert_eq!(
@@ -768,6 +1341,7 @@ mod tests {
             completed_at: Some("2025-06-01T10:25:00Z".to_string()),
             work_duration: 1500,
             break_duration: 300,
+            break_completed_at: None,
         };
         let json_str = serde_json::to_string(&original).unwrap();
         let restored: PomodoroSession = serde_json::from_str(&json_str).unwrap();
@@ -785,9 +1359,12 @@ mod tests {
             title: "Test".to_string(),
             completed: false,
             created_at: "2025-01-01".to_string(),
+            ..Default::default()
         };
         let json = serde_json::to_value(&t).unwrap();
         assert!(json.get("createdAt").is_some());
+        assert!(json.get("dueDate").is_some());
+        assert!(json.get("completedAt").is_some());
         assert!(json.get("created_at").is_none());
     }
 
@@ -798,12 +1375,111 @@ mod tests {
             title: "Write tests".to_string(),
             completed: true,
             created_at: "2025-01-01T12:00:00Z".to_string(),
+            priority: "high".to_string(),
+            due_date: Some("2025-01-03".to_string()),
+            notes: "Cover the edge cases".to_string(),
+            tags: vec!["work".to_string()],
+            completed_at: Some("2025-01-02T09:00:00Z".to_string()),
+            checklist: vec![ChecklistItem {
+                id: "c1".to_string(),
+                title: "Unit tests".to_string(),
+                completed: true,
+                completed_at: Some("2025-01-02T08:00:00Z".to_string()),
+            }],
+            completion_rule: "auto".to_string(),
+            recurrence: Some(TaskRecurrence {
+                frequency: "weekly".to_string(),
+                weekdays: vec![0, 4],
+                interval_days: 1,
+            }),
+            archived_at: Some("2025-01-05T00:00:00Z".to_string()),
+            deleted_at: None,
+            series_id: Some("t0".to_string()),
+            list_id: "work".to_string(),
+            sort_index: 3,
         };
         let json_str = serde_json::to_string(&original).unwrap();
         let restored: Task = serde_json::from_str(&json_str).unwrap();
         assert_eq!(restored.id, "t1");
         assert_eq!(restored.title, "Write tests");
         assert!(restored.completed);
+        assert_eq!(restored.priority, "high");
+        assert_eq!(restored.due_date.as_deref(), Some("2025-01-03"));
+        assert_eq!(restored.tags, vec!["work"]);
+        assert_eq!(restored.checklist.len(), 1);
+        assert!(restored.checklist[0].completed);
+        assert_eq!(restored.completion_rule, "auto");
+        assert_eq!(restored.recurrence.unwrap().weekdays, vec![0, 4]);
+        assert_eq!(restored.series_id.as_deref(), Some("t0"));
+        assert_eq!(restored.list_id, "work");
+        assert_eq!(restored.archived_at.as_deref(), Some("2025-01-05T00:00:00Z"));
+        assert!(restored.deleted_at.is_none());
+        assert_eq!(restored.sort_index, 3);
+    }
+
+    #[test]
+    fn task_default_has_no_created_at() {
+        let task: Task = serde_json::from_str(r#"{"id":"t1","title":"Old task"}"#).unwrap();
+        assert!(task.created_at.is_empty());
+    }
+
+    #[test]
+    fn task_deserialize_legacy_payload_uses_defaults() {
+        let json = r#"{"id":"t1","title":"Old task","completed":true,"createdAt":"2025-01-01T00:00:00Z"}"#;
+        let task: Task = serde_json::from_str(json).unwrap();
+        assert_eq!(task.title, "Old task");
+        assert!(task.completed);
+        assert_eq!(task.created_at, "2025-01-01T00:00:00Z");
+        assert_eq!(task.priority, "none");
+        assert!(task.due_date.is_none());
+        assert!(task.tags.is_empty());
+        assert!(task.completed_at.is_none());
+        assert!(task.checklist.is_empty());
+        assert_eq!(task.completion_rule, "manual");
+        assert_eq!(task.list_id, INBOX_TASK_LIST_ID);
+        assert_eq!(task.sort_index, 0);
+    }
+
+    #[test]
+    fn task_patch_empty_due_date_clears() {
+        let mut task = Task {
+            due_date: Some("2025-01-03".to_string()),
+            ..Default::default()
+        };
+        TaskPatch {
+            due_date: Some(String::new()),
+            notes: Some("later".to_string()),
+            ..Default::default()
+        }
+        .apply_to(&mut task);
+        assert!(task.due_date.is_none());
+        assert_eq!(task.notes, "later");
+    }
+
+    #[test]
+    fn task_patch_recurrence_sets_series_and_none_stops_it() {
+        let mut task = Task {
+            id: "t1".to_string(),
+            ..Default::default()
+        };
+        TaskPatch {
+            recurrence: Some(TaskRecurrence::default()),
+            ..Default::default()
+        }
+        .apply_to(&mut task);
+        assert_eq!(task.series_id.as_deref(), Some("t1"));
+        assert!(task.recurrence.is_some());
+
+        TaskPatch {
+            recurrence: Some(TaskRecurrence {
+                frequency: "none".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+        .apply_to(&mut task);
+        assert!(task.recurrence.is_none());
+        assert_eq!(task.series_id.as_deref(), Some("t1"));
     }
 
     // --- DailyGoal ---
@@ -816,6 +1492,8 @@ mod tests {
             target: 4,
             progress: 2,
             date: "2025-01-01".to_string(),
+            metric: "pomodoros".to_string(),
+            target_reason: None,
         };
         let json = serde_json::to_value(&g).unwrap();
         // DailyGoal fields are all single words or don't need renaming,
@@ -833,12 +1511,46 @@ mod tests {
             target: 2,
             progress: 1,
             date: "2025-06-15".to_string(),
+            metric: "tasks".to_string(),
+            target_reason: Some("Target raised".to_string()),
         };
         let json_str = serde_json::to_string(&original).unwrap();
         let restored: DailyGoal = serde_json::from_str(&json_str).unwrap();
         assert_eq!(restored.progress, 1);
         assert_eq!(restored.target, 2);
         assert_eq!(restored.date, "2025-06-15");
+        assert_eq!(restored.metric, "tasks");
+        assert_eq!(restored.target_reason.as_deref(), Some("Target raised"));
+    }
+
+    #[test]
+    fn daily_goal_legacy_payload_has_empty_metric() {
+        let json = r#"{"id":"breaks","description":"Take 3 breaks","target":3,"progress":1,"date":"2025-06-15"}"#;
+        let goal: DailyGoal = serde_json::from_str(json).unwrap();
+        assert_eq!(goal.metric, "");
+        assert_eq!(goal.target_reason, None);
+    }
+
+    #[test]
+    fn goal_template_patch_applies_fields() {
+        let mut template = GoalTemplate {
+            id: "g1".to_string(),
+            metric: "tasks".to_string(),
+            target: 2,
+            enabled: true,
+            weekdays: vec![],
+        };
+        GoalTemplatePatch {
+            target: Some(5),
+            enabled: Some(false),
+            weekdays: Some(vec![0, 2]),
+            ..Default::default()
+        }
+        .apply_to(&mut template);
+        assert_eq!(template.metric, "tasks");
+        assert_eq!(template.target, 5);
+        assert!(!template.enabled);
+        assert_eq!(template.weekdays, vec![0, 2]);
     }
 
     // --- Settings ---
@@ -916,6 +1628,19 @@ mod tests {
             focus_guardrails_work_only: true,
             focus_allowlist: vec!["localhost".to_string()],
             focus_blocklist: vec!["youtube.com".to_string()],
+            task_reward_clawback_enabled: true,
+            task_auto_archive_days: 14,
+            task_trash_retention_days: 60,
+            adaptive_goals_enabled: true,
+            adaptive_goal_stretch_percent: 20,
+            week_start_day: 6,
+            goal_reward_coins: 8,
+            perfect_day_bonus_coins: 40,
+            perfect_day_affection_boost: 12,
+            time_zone: "Europe/Berlin".to_string(),
+            day_start_hour: 4,
+            xp_curve: "table".to_string(),
+            streak_weekend_grace: true,
         };
         let json_str = serde_json::to_string(&original).unwrap();
         let restored: Settings = serde_json::from_str(&json_str).unwrap();
@@ -951,6 +1676,19 @@ mod tests {
         assert!(restored.focus_guardrails_enabled);
         assert_eq!(restored.focus_allowlist, vec!["localhost"]);
         assert_eq!(restored.focus_blocklist, vec!["youtube.com"]);
+        assert!(restored.task_reward_clawback_enabled);
+        assert_eq!(restored.task_auto_archive_days, 14);
+        assert_eq!(restored.task_trash_retention_days, 60);
+        assert!(restored.adaptive_goals_enabled);
+        assert_eq!(restored.adaptive_goal_stretch_percent, 20);
+        assert_eq!(restored.week_start_day, 6);
+        assert_eq!(restored.goal_reward_coins, 8);
+        assert_eq!(restored.perfect_day_bonus_coins, 40);
+        assert_eq!(restored.perfect_day_affection_boost, 12);
+        assert_eq!(restored.time_zone, "Europe/Berlin");
+        assert_eq!(restored.day_start_hour, 4);
+        assert_eq!(restored.xp_curve, "table");
+        assert!(restored.streak_weekend_grace);
     }
 
     #[test]
@@ -982,6 +1720,19 @@ mod tests {
             focus_guardrails_work_only: Some(false),
             focus_allowlist: Some(vec!["localhost".to_string()]),
             focus_blocklist: Some(vec!["youtube.com".to_string()]),
+            task_reward_clawback_enabled: Some(true),
+            task_auto_archive_days: Some(0),
+            task_trash_retention_days: Some(90),
+            adaptive_goals_enabled: Some(true),
+            adaptive_goal_stretch_percent: Some(15),
+            week_start_day: Some(2),
+            goal_reward_coins: Some(0),
+            perfect_day_bonus_coins: Some(50),
+            perfect_day_affection_boost: Some(5),
+            time_zone: Some("Asia/Tokyo".to_string()),
+            day_start_hour: Some(3),
+            xp_curve: Some("linear".to_string()),
+            streak_weekend_grace: Some(true),
         }
         .apply_to(&mut settings);
 
@@ -1010,6 +1761,19 @@ mod tests {
         assert!(!settings.focus_guardrails_work_only);
         assert_eq!(settings.focus_allowlist, vec!["localhost"]);
         assert_eq!(settings.focus_blocklist, vec!["youtube.com"]);
+        assert!(settings.task_reward_clawback_enabled);
+        assert_eq!(settings.task_auto_archive_days, 0);
+        assert_eq!(settings.task_trash_retention_days, 90);
+        assert!(settings.adaptive_goals_enabled);
+        assert_eq!(settings.adaptive_goal_stretch_percent, 15);
+        assert_eq!(settings.week_start_day, 2);
+        assert_eq!(settings.goal_reward_coins, 0);
+        assert_eq!(settings.perfect_day_bonus_coins, 50);
+        assert_eq!(settings.perfect_day_affection_boost, 5);
+        assert_eq!(settings.time_zone, "Asia/Tokyo");
+        assert_eq!(settings.day_start_hour, 3);
+        assert_eq!(settings.xp_curve, "linear");
+        assert!(settings.streak_weekend_grace);
     }
 
     #[test]
@@ -1019,6 +1783,8 @@ mod tests {
         assert!(settings.notifications_enabled);
         assert!(!settings.sounds_enabled);
         assert_eq!(settings.ui_theme, "sunrise");
+        assert_eq!(settings.xp_curve, LEGACY_XP_CURVE);
+        assert_eq!(Settings::default().xp_curve, "quadratic");
     }
 
     #[test]
@@ -1082,7 +1848,10 @@ mod tests {
             ui_theme: "mint".to_string(),
             pet_skin: "pixel".to_string(),
             pet_scene: "forest".to_string(),
-            accessories: vec!["party_hat".to_string()],
+            accessories: vec![],
+            equipped: [("head".to_string(), "party_hat".to_string())]
+                .into_iter()
+                .collect(),
         };
         let json = serde_json::to_value(&loadout).unwrap();
         assert!(json.get("uiTheme").is_some());
@@ -1090,6 +1859,22 @@ mod tests {
         assert!(json.get("petScene").is_some());
     }
 
+    #[test]
+    fn reward_ledger_entry_serializes_camel_case() {
+        let entry = RewardLedgerEntry {
+            source_kind: "task".to_string(),
+            source_id: "t1".to_string(),
+            reward_kind: "xp".to_string(),
+            amount: 10,
+            granted_at: "2026-01-01T00:00:00Z".to_string(),
+            revoked_at: None,
+        };
+        let json = serde_json::to_value(&entry).unwrap();
+        assert!(json.get("sourceKind").is_some());
+        assert!(json.get("rewardKind").is_some());
+        assert!(json.get("revokedAt").is_some());
+    }
+
     #[test]
     fn pet_event_serializes_camel_case() {
         let event = PetEvent {
@@ -0,0 +1,616 @@
+//! Recomputes `UserProgress` and the daily summaries from raw history.
+//!
+//! Active reward ledger entries are authoritative for everything they cover. Sessions and
+//! tasks completed before the ledger existed have no entry and are credited from their own
+//! timestamps instead. Goal completions from before the ledger left no other trace, so the
+//! stored counts are carried over as an opening balance. Guardrail counters and the freeze
+//! balance cannot be derived and are carried over unchanged.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use chrono::NaiveDate;
+
+use crate::{
+    clock::Clock,
+    models::{
+        DailySummary, PomodoroSession, ProgressChange, RewardLedgerEntry, SummaryRollup, Task,
+        UserProgress,
+    },
+    progression::{is_weekend, parse_date},
+    rewards::{
+        REWARD_COINS, REWARD_GOAL_PROGRESS, REWARD_XP, SOURCE_BREAK, SOURCE_GOAL, SOURCE_QUEST,
+        SOURCE_SESSION, SOURCE_TASK,
+    },
+};
+
+pub const PROGRESS_SCOPE: &str = "progress";
+
+/// Lifetime totals plus one summary per day, filled in as history is replayed.
+#[derive(Default)]
+struct Tally {
+    totals: DailySummary,
+    days: BTreeMap<String, DailySummary>,
+}
+
+impl Tally {
+    fn credit<F>(&mut self, day: Option<String>, apply: F)
+    where
+        F: Fn(&mut DailySummary),
+    {
+        apply(&mut self.totals);
+        if let Some(date) = day {
+            let summary = self
+                .days
+                .entry(date.clone())
+                .or_insert_with(|| DailySummary {
+                    date,
+                    ..Default::default()
+                });
+            apply(summary);
+        }
+    }
+}
+
+type LedgerKey<'a> = (&'a str, &'a str, &'a str);
+
+/// Keys of every ledger entry, revoked or not, to tell tracked rewards from legacy ones.
+fn index_ledger(ledger: &[RewardLedgerEntry]) -> HashSet<LedgerKey<'_>> {
+    ledger
+        .iter()
+        .map(|entry| {
+            (
+                entry.source_kind.as_str(),
+                entry.source_id.as_str(),
+                entry.reward_kind.as_str(),
+            )
+        })
+        .collect()
+}
+
+fn tally_history(
+    sessions: &[PomodoroSession],
+    tasks: &[Task],
+    ledger: &[RewardLedgerEntry],
+    clock: &Clock,
+) -> Tally {
+    let mut tally = Tally::default();
+    let index = index_ledger(ledger);
+    let work_minutes: HashMap<&str, u32> = sessions
+        .iter()
+        .map(|session| (session.id.as_str(), session.work_duration / 60))
+        .collect();
+
+    for entry in ledger.iter().filter(|entry| entry.revoked_at.is_none()) {
+        let day = clock.day_of_timestamp(&entry.granted_at);
+        let amount = entry.amount;
+        match (entry.source_kind.as_str(), entry.reward_kind.as_str()) {
+            (SOURCE_SESSION, REWARD_XP) => {
+                let minutes = work_minutes
+                    .get(entry.source_id.as_str())
+                    .copied()
+                    .unwrap_or(0);
+                tally.credit(day, |summary| {
+                    summary.sessions_completed += 1;
+                    summary.focus_minutes += minutes;
+                    summary.xp_earned += amount;
+                });
+            }
+            (SOURCE_TASK, REWARD_XP) => tally.credit(day, |summary| {
+                summary.tasks_completed += 1;
+                summary.xp_earned += amount;
+            }),
+            (SOURCE_GOAL, REWARD_XP) => tally.credit(day, |summary| {
+                summary.goals_completed += 1;
+                summary.xp_earned += amount;
+            }),
+            (_, REWARD_XP) => tally.credit(day, |summary| summary.xp_earned += amount),
+            // Quest coins are paid straight into the balance and never reach the summaries.
+            (SOURCE_QUEST, REWARD_COINS) => {}
+            (_, REWARD_COINS) => tally.credit(day, |summary| summary.coins_earned += amount),
+            (SOURCE_BREAK, REWARD_GOAL_PROGRESS) => {
+                tally.credit(day, |summary| summary.breaks_taken += 1)
+            }
+            _ => {}
+        }
+    }
+
+    for session in sessions {
+        let Some(completed_at) = session.completed_at.as_deref() else {
+            continue;
+        };
+        let id = session.id.as_str();
+        if !index.contains(&(SOURCE_SESSION, id, REWARD_XP)) {
+            let minutes = session.work_duration / 60;
+            tally.credit(clock.day_of_timestamp(completed_at), |summary| {
+                summary.sessions_completed += 1;
+                summary.focus_minutes += minutes;
+                summary.xp_earned += crate::progression::XP_PER_POMODORO;
+            });
+        }
+        if !index.contains(&(SOURCE_SESSION, id, REWARD_COINS)) {
+            tally.credit(clock.day_of_timestamp(completed_at), |summary| {
+                summary.coins_earned += crate::commands::pomodoro::COINS_PER_POMODORO;
+            });
+        }
+        if let Some(break_completed_at) = session.break_completed_at.as_deref() {
+            if !index.contains(&(SOURCE_BREAK, id, REWARD_GOAL_PROGRESS)) {
+                tally.credit(clock.day_of_timestamp(break_completed_at), |summary| {
+                    summary.breaks_taken += 1;
+                });
+            }
+        }
+    }
+
+    for task in tasks.iter().filter(|task| task.completed) {
+        let Some(completed_at) = task.completed_at.as_deref() else {
+            continue;
+        };
+        if index.contains(&(SOURCE_TASK, task.id.as_str(), REWARD_XP)) {
+            continue;
+        }
+        tally.credit(clock.day_of_timestamp(completed_at), |summary| {
+            summary.tasks_completed += 1;
+            summary.xp_earned += crate::progression::XP_PER_TASK_COMPLETION;
+        });
+    }
+
+    tally
+}
+
+/// Carries over goal completions recorded before the ledger's first day.
+///
+/// Stored days up to then keep any goals the ledger doesn't account for, along with the
+/// XP they paid. Months archived entirely before the ledger add their goal XP to the totals.
+fn carry_pre_ledger_goals(
+    tally: &mut Tally,
+    summaries: &[DailySummary],
+    rollups: &[SummaryRollup],
+    ledger: &[RewardLedgerEntry],
+    clock: &Clock,
+) {
+    let ledger_start = ledger
+        .iter()
+        .filter_map(|entry| clock.day_of_timestamp(&entry.granted_at))
+        .min();
+    let pre_ledger = |date: &str| ledger_start.as_deref().map_or(true, |start| date <= start);
+
+    for stored in summaries.iter().filter(|stored| pre_ledger(&stored.date)) {
+        let tracked = tally
+            .days
+            .get(&stored.date)
+            .map_or(0, |summary| summary.goals_completed);
+        let legacy = stored.goals_completed.saturating_sub(tracked);
+        if legacy > 0 {
+            tally.credit(Some(stored.date.clone()), |summary| {
+                summary.goals_completed += legacy;
+                summary.xp_earned += legacy * crate::progression::XP_PER_GOAL_COMPLETION;
+            });
+        }
+    }
+
+    let archived_goals: u32 = rollups
+        .iter()
+        .filter(|rollup| rollup.period == "month")
+        .filter(|rollup| {
+            ledger_start
+                .as_deref()
+                .map_or(true, |start| rollup.end.as_str() < start)
+        })
+        .map(|rollup| rollup.goals_completed)
+        .sum();
+    tally.totals.goals_completed += archived_goals;
+    tally.totals.xp_earned += archived_goals * crate::progression::XP_PER_GOAL_COMPLETION;
+}
+
+/// Replays the streak over `active_days`, bridging gaps made only of frozen or grace days.
+/// Returns the current and the longest streak.
+fn replay_streak(
+    active_days: &BTreeSet<NaiveDate>,
+    frozen_days: &HashSet<NaiveDate>,
+    weekend_grace: bool,
+) -> (u32, u32) {
+    let mut streak = 0;
+    let mut longest = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for &day in active_days {
+        let bridged = previous.is_some_and(|prev| {
+            prev.iter_days()
+                .skip(1)
+                .take_while(|missed| *missed < day)
+                .all(|missed| {
+                    frozen_days.contains(&missed) || (weekend_grace && is_weekend(missed))
+                })
+        });
+        streak = if bridged { streak + 1 } else { 1 };
+        longest = longest.max(streak);
+        previous = Some(day);
+    }
+    (streak, longest)
+}
+
+/// Rebuilds progress and the most recent daily summaries from raw history.
+///
+/// The stored `last_active_date` counts as an active day, because opening the app keeps
+/// the streak alive even without a session.
+#[allow(clippy::too_many_arguments)]
+pub fn rebuild(
+    progress: &UserProgress,
+    summaries: &[DailySummary],
+    rollups: &[SummaryRollup],
+    sessions: &[PomodoroSession],
+    tasks: &[Task],
+    ledger: &[RewardLedgerEntry],
+    clock: &Clock,
+    weekend_grace: bool,
+    xp_curve: &str,
+) -> (UserProgress, Vec<DailySummary>) {
+    let mut tally = tally_history(sessions, tasks, ledger, clock);
+    carry_pre_ledger_goals(&mut tally, summaries, rollups, ledger, clock);
+    let Tally { totals, mut days } = tally;
+
+    let mut active_days: BTreeSet<NaiveDate> =
+        days.keys().filter_map(|date| parse_date(date)).collect();
+    if let Some(last) = progress.last_active_date.as_deref().and_then(parse_date) {
+        active_days.insert(last);
+    }
+    let frozen_days: HashSet<NaiveDate> = progress
+        .streak_freeze_log
+        .iter()
+        .filter_map(|entry| parse_date(&entry.date))
+        .collect();
+    let (streak_days, longest_streak) = replay_streak(&active_days, &frozen_days, weekend_grace);
+
+    let mut rebuilt = progress.clone();
+    rebuilt.xp_total = totals.xp_earned;
+    rebuilt.total_sessions = totals.sessions_completed;
+    rebuilt.total_focus_minutes = totals.focus_minutes;
+    rebuilt.total_tasks_completed = totals.tasks_completed;
+    rebuilt.streak_days = streak_days;
+    rebuilt.longest_streak = longest_streak;
+    rebuilt.last_active_date = active_days
+        .iter()
+        .next_back()
+        .map(|day| day.format("%Y-%m-%d").to_string());
+    crate::leveling::apply_level(&mut rebuilt, xp_curve);
+
+    for stored in summaries {
+        let summary = days
+            .entry(stored.date.clone())
+            .or_insert_with(|| DailySummary {
+                date: stored.date.clone(),
+                ..Default::default()
+            });
+        summary.guardrails_interventions = stored.guardrails_interventions;
+        summary.high_nudges = stored.high_nudges;
+    }
+    let mut rebuilt_summaries: Vec<DailySummary> = days.into_values().rev().collect();
+    rebuilt_summaries.truncate(crate::progression::MAX_DAILY_SUMMARIES);
+
+    (rebuilt, rebuilt_summaries)
+}
+
+fn progress_fields(progress: &UserProgress) -> [(&'static str, u32); 7] {
+    [
+        ("xpTotal", progress.xp_total),
+        ("level", progress.level),
+        ("streakDays", progress.streak_days),
+        ("longestStreak", progress.longest_streak),
+        ("totalSessions", progress.total_sessions),
+        ("totalFocusMinutes", progress.total_focus_minutes),
+        ("totalTasksCompleted", progress.total_tasks_completed),
+    ]
+}
+
+fn summary_fields(summary: &DailySummary) -> [(&'static str, u32); 9] {
+    [
+        ("sessionsCompleted", summary.sessions_completed),
+        ("focusMinutes", summary.focus_minutes),
+        ("tasksCompleted", summary.tasks_completed),
+        ("goalsCompleted", summary.goals_completed),
+        ("coinsEarned", summary.coins_earned),
+        ("xpEarned", summary.xp_earned),
+        ("guardrailsInterventions", summary.guardrails_interventions),
+        ("highNudges", summary.high_nudges),
+        ("breaksTaken", summary.breaks_taken),
+    ]
+}
+
+fn push_changes<const N: usize>(
+    changes: &mut Vec<ProgressChange>,
+    scope: &str,
+    before: [(&'static str, u32); N],
+    after: [(&'static str, u32); N],
+) {
+    for ((field, old), (_, new)) in before.into_iter().zip(after) {
+        if old != new {
+            changes.push(ProgressChange {
+                scope: scope.to_string(),
+                field: field.to_string(),
+                before: old,
+                after: new,
+            });
+        }
+    }
+}
+
+/// Lists every counter that differs, progress first and then summaries newest first.
+/// A day missing on either side compares against zeroes.
+pub fn diff(
+    progress: &UserProgress,
+    summaries: &[DailySummary],
+    rebuilt_progress: &UserProgress,
+    rebuilt_summaries: &[DailySummary],
+) -> Vec<ProgressChange> {
+    let mut changes = Vec::new();
+    push_changes(
+        &mut changes,
+        PROGRESS_SCOPE,
+        progress_fields(progress),
+        progress_fields(rebuilt_progress),
+    );
+
+    let dates: BTreeSet<&str> = summaries
+        .iter()
+        .chain(rebuilt_summaries)
+        .map(|summary| summary.date.as_str())
+        .collect();
+    for date in dates.into_iter().rev() {
+        let find = |list: &[DailySummary]| {
+            list.iter()
+                .find(|summary| summary.date == date)
+                .cloned()
+                .unwrap_or_else(|| DailySummary {
+                    date: date.to_string(),
+                    ..Default::default()
+                })
+        };
+        push_changes(
+            &mut changes,
+            date,
+            summary_fields(&find(summaries)),
+            summary_fields(&find(rebuilt_summaries)),
+        );
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc() -> Clock {
+        Clock::new(Some(chrono_tz::UTC), 0)
+    }
+
+    fn session(id: &str, completed_at: &str) -> PomodoroSession {
+        PomodoroSession {
+            id: id.to_string(),
+            started_at: completed_at.to_string(),
+            completed_at: Some(completed_at.to_string()),
+            work_duration: 1500,
+            break_duration: 300,
+            break_completed_at: None,
+        }
+    }
+
+    fn entry(source: &str, id: &str, reward: &str, amount: u32, at: &str) -> RewardLedgerEntry {
+        RewardLedgerEntry {
+            source_kind: source.to_string(),
+            source_id: id.to_string(),
+            reward_kind: reward.to_string(),
+            amount,
+            granted_at: at.to_string(),
+            revoked_at: None,
+        }
+    }
+
+    fn day(date: &str) -> NaiveDate {
+        parse_date(date).unwrap()
+    }
+
+    #[test]
+    fn legacy_sessions_and_ledger_rewards_are_both_counted() {
+        let sessions = vec![
+            session("legacy", "2026-03-01T10:00:00Z"),
+            session("tracked", "2026-03-02T10:00:00Z"),
+        ];
+        let ledger = vec![
+            entry(
+                SOURCE_SESSION,
+                "tracked",
+                REWARD_XP,
+                25,
+                "2026-03-02T10:00:01Z",
+            ),
+            entry(
+                SOURCE_SESSION,
+                "tracked",
+                REWARD_COINS,
+                10,
+                "2026-03-02T10:00:01Z",
+            ),
+            entry(
+                SOURCE_GOAL,
+                "2026-03-02:pomodoros",
+                REWARD_XP,
+                15,
+                "2026-03-02T11:00:00Z",
+            ),
+            entry(
+                crate::rewards::SOURCE_PERIOD_GOAL,
+                "2026-03-02:weekly_pomodoros",
+                REWARD_XP,
+                50,
+                "2026-03-02T11:00:00Z",
+            ),
+        ];
+        let (progress, summaries) = rebuild(
+            &UserProgress::default(),
+            &[],
+            &[],
+            &sessions,
+            &[],
+            &ledger,
+            &utc(),
+            false,
+            "quadratic",
+        );
+
+        assert_eq!(progress.total_sessions, 2);
+        assert_eq!(progress.total_focus_minutes, 50);
+        assert_eq!(progress.xp_total, 115);
+        assert_eq!(progress.streak_days, 2);
+        assert_eq!(progress.last_active_date.as_deref(), Some("2026-03-02"));
+        assert_eq!(summaries[0].date, "2026-03-02");
+        assert_eq!(summaries[0].goals_completed, 1);
+        assert_eq!(summaries[0].coins_earned, 10);
+        assert_eq!(summaries[1].coins_earned, 10);
+    }
+
+    #[test]
+    fn revoked_task_rewards_are_not_counted() {
+        let mut revoked = entry(SOURCE_TASK, "t1", REWARD_XP, 10, "2026-03-02T10:00:00Z");
+        revoked.revoked_at = Some("2026-03-02T12:00:00Z".to_string());
+        let task = Task {
+            id: "t1".to_string(),
+            completed: true,
+            completed_at: Some("2026-03-02T10:00:00Z".to_string()),
+            ..Default::default()
+        };
+        let (progress, _) = rebuild(
+            &UserProgress::default(),
+            &[],
+            &[],
+            &[],
+            &[task],
+            &[revoked],
+            &utc(),
+            false,
+            "quadratic",
+        );
+        assert_eq!(progress.total_tasks_completed, 0);
+        assert_eq!(progress.xp_total, 0);
+    }
+
+    #[test]
+    fn guardrail_counters_are_carried_over() {
+        let stored = DailySummary {
+            date: "2026-03-01".to_string(),
+            sessions_completed: 9,
+            guardrails_interventions: 3,
+            high_nudges: 1,
+            ..Default::default()
+        };
+        let (_, summaries) = rebuild(
+            &UserProgress::default(),
+            std::slice::from_ref(&stored),
+            &[],
+            &[],
+            &[],
+            &[],
+            &utc(),
+            false,
+            "quadratic",
+        );
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].sessions_completed, 0);
+        assert_eq!(summaries[0].guardrails_interventions, 3);
+        assert_eq!(summaries[0].high_nudges, 1);
+    }
+
+    #[test]
+    fn pre_ledger_goal_completions_are_carried_over() {
+        let stored = vec![
+            DailySummary {
+                date: "2026-03-02".to_string(),
+                goals_completed: 2,
+                ..Default::default()
+            },
+            DailySummary {
+                date: "2026-03-01".to_string(),
+                goals_completed: 3,
+                ..Default::default()
+            },
+        ];
+        let archived = SummaryRollup {
+            period: "month".to_string(),
+            start: "2026-01-01".to_string(),
+            end: "2026-01-31".to_string(),
+            goals_completed: 4,
+            ..Default::default()
+        };
+        let ledger = vec![entry(
+            SOURCE_GOAL,
+            "2026-03-02:pomodoros",
+            REWARD_XP,
+            15,
+            "2026-03-02T11:00:00Z",
+        )];
+        let (progress, summaries) = rebuild(
+            &UserProgress::default(),
+            &stored,
+            &[archived],
+            &[],
+            &[],
+            &ledger,
+            &utc(),
+            false,
+            "quadratic",
+        );
+
+        assert_eq!(summaries[0].goals_completed, 2);
+        assert_eq!(summaries[0].xp_earned, 30);
+        assert_eq!(summaries[1].goals_completed, 3);
+        assert_eq!(summaries[1].xp_earned, 45);
+        assert_eq!(progress.xp_total, 15 * 9);
+    }
+
+    #[test]
+    fn replay_streak_bridges_frozen_and_weekend_days() {
+        // 2026-03-06 is a Friday.
+        let active: BTreeSet<NaiveDate> = ["2026-03-05", "2026-03-06", "2026-03-09", "2026-03-11"]
+            .into_iter()
+            .map(day)
+            .collect();
+        let frozen: HashSet<NaiveDate> = [day("2026-03-10")].into_iter().collect();
+
+        assert_eq!(replay_streak(&active, &frozen, true), (4, 4));
+        assert_eq!(replay_streak(&active, &frozen, false), (2, 2));
+        assert_eq!(replay_streak(&active, &HashSet::new(), true), (1, 3));
+    }
+
+    #[test]
+    fn diff_reports_only_changed_counters() {
+        let before = UserProgress {
+            xp_total: 40,
+            ..Default::default()
+        };
+        let after = UserProgress {
+            xp_total: 25,
+            ..Default::default()
+        };
+        let rebuilt_day = DailySummary {
+            date: "2026-03-02".to_string(),
+            sessions_completed: 1,
+            ..Default::default()
+        };
+        let changes = diff(&before, &[], &after, &[rebuilt_day]);
+        assert_eq!(
+            changes,
+            vec![
+                ProgressChange {
+                    scope: PROGRESS_SCOPE.to_string(),
+                    field: "xpTotal".to_string(),
+                    before: 40,
+                    after: 25,
+                },
+                ProgressChange {
+                    scope: "2026-03-02".to_string(),
+                    field: "sessionsCompleted".to_string(),
+                    before: 0,
+                    after: 1,
+                },
+            ]
+        );
+    }
+}
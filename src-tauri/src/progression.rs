@@ -4,31 +4,77 @@ use tauri_plugin_store::StoreExt;
 
 use crate::{
     events::{EVENT_ANALYTICS_CHANGED, EVENT_PROFILE_CHANGED},
-    models::{DailySummary, UserProgress},
+    models::{DailySummary, LevelReward, Settings, StreakFreezeUse, UserProgress},
 };
 
-const XP_PER_POMODORO: u32 = 25;
-const XP_PER_TASK_COMPLETION: u32 = 10;
-const XP_PER_GOAL_COMPLETION: u32 = 15;
+pub const XP_PER_POMODORO: u32 = 25;
+pub const XP_PER_TASK_COMPLETION: u32 = 10;
+pub const XP_PER_SUBTASK_COMPLETION: u32 = 3;
+pub const XP_PER_GOAL_COMPLETION: u32 = 15;
+pub const XP_PER_WEEKLY_GOAL_COMPLETION: u32 = 60;
+pub const XP_PER_MONTHLY_GOAL_COMPLETION: u32 = 200;
+pub const STREAK_FREEZE_COST: u32 = 50;
+const MAX_STREAK_FREEZES: u32 = 3;
+/// A freeze is earned each time the streak reaches a multiple of this many days.
+const STREAK_FREEZE_EARN_INTERVAL_DAYS: u32 = 7;
+const MAX_STREAK_FREEZE_LOG: usize = 100;
+pub(crate) const MAX_DAILY_SUMMARIES: usize = 90;
 
-fn today() -> String {
-    chrono::Utc::now().format("%Y-%m-%d").to_string()
+pub(crate) fn parse_date(date: &str) -> Option<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
 }
 
-fn xp_to_level(xp_total: u32) -> u32 {
-    1 + (xp_total / 100)
+pub(crate) fn is_weekend(date: chrono::NaiveDate) -> bool {
+    use chrono::Datelike;
+    matches!(date.weekday(), chrono::Weekday::Sat | chrono::Weekday::Sun)
 }
 
-fn parse_date(date: &str) -> Option<chrono::NaiveDate> {
-    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+/// Covers the days missed between `prev` and `current` with weekend grace and freezes.
+///
+/// Freezes are only consumed when they cover the whole gap; returns whether the streak
+/// survives. Covered days keep the streak alive but do not add to it.
+fn bridge_missed_days(
+    progress: &mut UserProgress,
+    prev: chrono::NaiveDate,
+    current: chrono::NaiveDate,
+    weekend_grace: bool,
+) -> bool {
+    if current <= prev {
+        return false;
+    }
+    let missed: Vec<chrono::NaiveDate> = prev
+        .iter_days()
+        .skip(1)
+        .take_while(|day| *day < current)
+        .filter(|day| !(weekend_grace && is_weekend(*day)))
+        .collect();
+    if missed.len() > progress.streak_freezes as usize {
+        return false;
+    }
+
+    let used_at = chrono::Utc::now().to_rfc3339();
+    for day in missed {
+        progress.streak_freezes -= 1;
+        progress.streak_freeze_log.push(StreakFreezeUse {
+            date: day.format("%Y-%m-%d").to_string(),
+            used_at: used_at.clone(),
+        });
+    }
+    if progress.streak_freeze_log.len() > MAX_STREAK_FREEZE_LOG {
+        let overflow = progress.streak_freeze_log.len() - MAX_STREAK_FREEZE_LOG;
+        progress.streak_freeze_log.drain(..overflow);
+    }
+    true
 }
 
-fn update_streak(progress: &mut UserProgress, active_date: &str) {
+fn update_streak(progress: &mut UserProgress, active_date: &str, weekend_grace: bool) {
     match progress.last_active_date.as_deref() {
         Some(last) if last == active_date => {}
         Some(last) => {
             let continues_streak = match (parse_date(last), parse_date(active_date)) {
-                (Some(prev), Some(current)) => current.signed_duration_since(prev).num_days() == 1,
+                (Some(prev), Some(current)) => {
+                    bridge_missed_days(progress, prev, current, weekend_grace)
+                }
                 _ => false,
             };
             progress.streak_days = if continues_streak {
@@ -36,6 +82,9 @@ fn update_streak(progress: &mut UserProgress, active_date: &str) {
             } else {
                 1
             };
+            if progress.streak_days % STREAK_FREEZE_EARN_INTERVAL_DAYS == 0 {
+                progress.streak_freezes = (progress.streak_freezes + 1).min(MAX_STREAK_FREEZES);
+            }
             progress.longest_streak = progress.longest_streak.max(progress.streak_days);
             progress.last_active_date = Some(active_date.to_string());
         }
@@ -47,7 +96,37 @@ fn update_streak(progress: &mut UserProgress, active_date: &str) {
     }
 }
 
+/// Recomputes the level on the configured curve and claims rewards for levels reached.
+fn settle_level(
+    app: &AppHandle,
+    settings: &Settings,
+    progress: &mut UserProgress,
+) -> Result<Vec<LevelReward>, String> {
+    crate::leveling::apply_level(progress, &settings.xp_curve);
+    crate::leveling::claim_level_rewards(app, progress)
+}
+
 fn mutate_progress<F>(app: &AppHandle, mutator: F) -> Result<UserProgress, String>
+where
+    F: FnOnce(&mut UserProgress),
+{
+    write_progress(app, true, mutator)
+}
+
+/// Like `mutate_progress` for corrections such as revoked rewards, which are not
+/// activity and must neither extend the streak nor spend or earn freezes.
+fn correct_progress<F>(app: &AppHandle, mutator: F) -> Result<UserProgress, String>
+where
+    F: FnOnce(&mut UserProgress),
+{
+    write_progress(app, false, mutator)
+}
+
+fn write_progress<F>(
+    app: &AppHandle,
+    counts_as_activity: bool,
+    mutator: F,
+) -> Result<UserProgress, String>
 where
     F: FnOnce(&mut UserProgress),
 {
@@ -57,16 +136,36 @@ where
         .and_then(|v| serde_json::from_value(v).ok())
         .unwrap_or_default();
 
-    update_streak(&mut progress, &today());
+    let settings = crate::commands::settings::get_settings(app.clone())?;
+    let previous_level = progress.level;
+    if counts_as_activity {
+        update_streak(
+            &mut progress,
+            &crate::clock::today(app),
+            settings.streak_weekend_grace,
+        );
+    }
     mutator(&mut progress);
-    progress.level = xp_to_level(progress.xp_total);
+    let rewards = settle_level(app, &settings, &mut progress)?;
 
     store.set("user_progress", json!(progress));
     let _ = app.emit(EVENT_PROFILE_CHANGED, &progress);
+    crate::leveling::announce_level_up(app, previous_level, progress.level, rewards)?;
     Ok(progress)
 }
 
 fn mutate_today_summary<F>(app: &AppHandle, mutator: F) -> Result<Vec<DailySummary>, String>
+where
+    F: FnOnce(&mut DailySummary),
+{
+    mutate_summary_for_date(app, &crate::clock::today(app), mutator)
+}
+
+fn mutate_summary_for_date<F>(
+    app: &AppHandle,
+    date: &str,
+    mutator: F,
+) -> Result<Vec<DailySummary>, String>
 where
     F: FnOnce(&mut DailySummary),
 {
@@ -76,7 +175,7 @@ where
         .and_then(|v| serde_json::from_value(v).ok())
         .unwrap_or_default();
 
-    let date = today();
+    let date = date.to_string();
     let idx = summaries
         .iter()
         .position(|s| s.date == date)
@@ -93,8 +192,9 @@ where
     }
 
     summaries.sort_by(|a, b| b.date.cmp(&a.date));
-    if summaries.len() > 90 {
-        summaries.truncate(90);
+    if summaries.len() > MAX_DAILY_SUMMARIES {
+        let aged_out = summaries.split_off(MAX_DAILY_SUMMARIES);
+        crate::commands::analytics::archive_summaries(app, &aged_out)?;
     }
 
     store.set("daily_summaries", json!(summaries));
@@ -109,12 +209,45 @@ pub fn get_user_progress(app: &AppHandle) -> Result<UserProgress, String> {
         .and_then(|v| serde_json::from_value(v).ok())
         .unwrap_or_default();
 
-    update_streak(&mut progress, &today());
-    progress.level = xp_to_level(progress.xp_total);
+    let settings = crate::commands::settings::get_settings(app.clone())?;
+    let previous_level = progress.level;
+    update_streak(
+        &mut progress,
+        &crate::clock::today(app),
+        settings.streak_weekend_grace,
+    );
+    let rewards = settle_level(app, &settings, &mut progress)?;
     store.set("user_progress", json!(progress));
+    crate::leveling::announce_level_up(app, previous_level, progress.level, rewards)?;
     Ok(progress)
 }
 
+/// Spends coins on a streak freeze, up to the freeze cap.
+pub fn buy_streak_freeze(app: &AppHandle) -> Result<UserProgress, String> {
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    let progress: UserProgress = store
+        .get("user_progress")
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    if progress.streak_freezes >= MAX_STREAK_FREEZES {
+        return Err(format!(
+            "You can hold at most {} streak freezes",
+            MAX_STREAK_FREEZES
+        ));
+    }
+
+    crate::commands::coins::debit_coins(
+        app,
+        STREAK_FREEZE_COST,
+        crate::commands::coins::COIN_SOURCE_STREAK_FREEZE,
+        Some(&crate::clock::today(app)),
+    )?;
+
+    mutate_progress(app, |progress| {
+        progress.streak_freezes = (progress.streak_freezes + 1).min(MAX_STREAK_FREEZES);
+    })
+}
+
 pub fn get_daily_summaries(app: &AppHandle, days: u32) -> Result<Vec<DailySummary>, String> {
     let store = app.store("store.json").map_err(|e| e.to_string())?;
     let mut summaries: Vec<DailySummary> = store
@@ -147,6 +280,13 @@ pub fn record_focus_session(
     Ok(())
 }
 
+pub fn record_break_taken(app: &AppHandle) -> Result<(), String> {
+    mutate_today_summary(app, |summary| {
+        summary.breaks_taken += 1;
+    })?;
+    Ok(())
+}
+
 pub fn record_task_completion(app: &AppHandle) -> Result<(), String> {
     mutate_progress(app, |progress| {
         progress.total_tasks_completed += 1;
@@ -161,19 +301,83 @@ pub fn record_task_completion(app: &AppHandle) -> Result<(), String> {
     Ok(())
 }
 
-pub fn record_goal_completion(app: &AppHandle) -> Result<(), String> {
+/// Reverses `record_task_completion` against the day the reward was originally granted.
+/// `xp` is the amount the revoked ledger entry actually paid.
+pub fn revoke_task_completion(app: &AppHandle, granted_at: &str, xp: u32) -> Result<(), String> {
+    correct_progress(app, |progress| {
+        progress.total_tasks_completed = progress.total_tasks_completed.saturating_sub(1);
+        progress.xp_total = progress.xp_total.saturating_sub(xp);
+    })?;
+
+    if let Some(granted_date) = crate::clock::day_of_timestamp(app, granted_at) {
+        mutate_summary_for_date(app, &granted_date, |summary| {
+            summary.tasks_completed = summary.tasks_completed.saturating_sub(1);
+            summary.xp_earned = summary.xp_earned.saturating_sub(xp);
+        })?;
+    }
+
+    Ok(())
+}
+
+pub fn record_subtask_completion(app: &AppHandle) -> Result<(), String> {
     mutate_progress(app, |progress| {
-        progress.xp_total += XP_PER_GOAL_COMPLETION;
+        progress.xp_total += XP_PER_SUBTASK_COMPLETION;
+    })?;
+
+    mutate_today_summary(app, |summary| {
+        summary.xp_earned += XP_PER_SUBTASK_COMPLETION;
+    })?;
+
+    Ok(())
+}
+
+pub fn revoke_subtask_completion(app: &AppHandle, granted_at: &str, xp: u32) -> Result<(), String> {
+    correct_progress(app, |progress| {
+        progress.xp_total = progress.xp_total.saturating_sub(xp);
+    })?;
+
+    if let Some(granted_date) = crate::clock::day_of_timestamp(app, granted_at) {
+        mutate_summary_for_date(app, &granted_date, |summary| {
+            summary.xp_earned = summary.xp_earned.saturating_sub(xp);
+        })?;
+    }
+
+    Ok(())
+}
+
+pub fn record_goal_completion(app: &AppHandle, xp: u32) -> Result<(), String> {
+    mutate_progress(app, |progress| {
+        progress.xp_total += xp;
     })?;
 
     mutate_today_summary(app, |summary| {
         summary.goals_completed += 1;
-        summary.xp_earned += XP_PER_GOAL_COMPLETION;
+        summary.xp_earned += xp;
     })?;
 
     Ok(())
 }
 
+/// Weekly and monthly goals pay XP without counting towards the daily goals completed.
+pub fn record_period_goal_completion(app: &AppHandle, xp: u32) -> Result<(), String> {
+    mutate_progress(app, |progress| {
+        progress.xp_total += xp;
+    })?;
+
+    mutate_today_summary(app, |summary| {
+        summary.xp_earned += xp;
+    })?;
+
+    Ok(())
+}
+
+pub fn record_coins_earned(app: &AppHandle, coins: u32) -> Result<(), String> {
+    mutate_today_summary(app, |summary| {
+        summary.coins_earned += coins;
+    })?;
+    Ok(())
+}
+
 pub fn record_guardrail_intervention(app: &AppHandle, nudge_level: &str) -> Result<(), String> {
     mutate_today_summary(app, |summary| {
         summary.guardrails_interventions += 1;
@@ -183,3 +387,72 @@ pub fn record_guardrail_intervention(app: &AppHandle, nudge_level: &str) -> Resu
     })?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress_on(last_active: &str, streak: u32, freezes: u32) -> UserProgress {
+        UserProgress {
+            streak_days: streak,
+            longest_streak: streak,
+            last_active_date: Some(last_active.to_string()),
+            streak_freezes: freezes,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn missed_day_resets_streak_without_freezes() {
+        let mut progress = progress_on("2026-03-02", 4, 0);
+        update_streak(&mut progress, "2026-03-04", false);
+        assert_eq!(progress.streak_days, 1);
+        assert_eq!(progress.longest_streak, 4);
+    }
+
+    #[test]
+    fn freezes_cover_missed_days_and_are_logged() {
+        let mut progress = progress_on("2026-03-02", 4, 2);
+        update_streak(&mut progress, "2026-03-05", false);
+        assert_eq!(progress.streak_days, 5);
+        assert_eq!(progress.streak_freezes, 0);
+        let covered: Vec<&str> = progress
+            .streak_freeze_log
+            .iter()
+            .map(|entry| entry.date.as_str())
+            .collect();
+        assert_eq!(covered, vec!["2026-03-03", "2026-03-04"]);
+    }
+
+    #[test]
+    fn freezes_are_kept_when_gap_is_too_long() {
+        let mut progress = progress_on("2026-03-02", 4, 1);
+        update_streak(&mut progress, "2026-03-05", false);
+        assert_eq!(progress.streak_days, 1);
+        assert_eq!(progress.streak_freezes, 1);
+        assert!(progress.streak_freeze_log.is_empty());
+    }
+
+    #[test]
+    fn weekend_grace_skips_saturday_and_sunday() {
+        // 2026-03-06 is a Friday, 2026-03-09 the following Monday.
+        let mut progress = progress_on("2026-03-06", 5, 0);
+        update_streak(&mut progress, "2026-03-09", true);
+        assert_eq!(progress.streak_days, 6);
+
+        let mut strict = progress_on("2026-03-06", 5, 0);
+        update_streak(&mut strict, "2026-03-09", false);
+        assert_eq!(strict.streak_days, 1);
+    }
+
+    #[test]
+    fn freezes_are_earned_every_interval_up_to_cap() {
+        let mut progress = progress_on("2026-03-06", STREAK_FREEZE_EARN_INTERVAL_DAYS - 1, 0);
+        update_streak(&mut progress, "2026-03-07", false);
+        assert_eq!(progress.streak_freezes, 1);
+
+        let mut capped = progress_on("2026-03-06", 13, MAX_STREAK_FREEZES);
+        update_streak(&mut capped, "2026-03-07", false);
+        assert_eq!(capped.streak_freezes, MAX_STREAK_FREEZES);
+    }
+}
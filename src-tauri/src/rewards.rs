@@ -0,0 +1,309 @@
+use serde_json::json;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use crate::models::RewardLedgerEntry;
+
+/// Entries are never dropped: each one is the key that stops a reward from being paid twice.
+const REWARD_LEDGER_KEY: &str = "reward_ledger";
+
+pub const SOURCE_TASK: &str = "task";
+pub const SOURCE_SUBTASK: &str = "subtask";
+pub const SOURCE_SESSION: &str = "session";
+pub const SOURCE_BREAK: &str = "break";
+pub const SOURCE_GOAL: &str = "goal";
+pub const SOURCE_PERIOD_GOAL: &str = "period_goal";
+pub const SOURCE_PERFECT_DAY: &str = "perfect_day";
+pub const SOURCE_LEVEL: &str = "level";
+pub const SOURCE_QUEST: &str = "quest";
+
+pub const REWARD_XP: &str = "xp";
+pub const REWARD_COINS: &str = "coins";
+pub const REWARD_GOAL_PROGRESS: &str = "goal_progress";
+
+fn find_entry<'a>(
+    entries: &'a mut [RewardLedgerEntry],
+    source_kind: &str,
+    source_id: &str,
+    reward_kind: &str,
+) -> Option<&'a mut RewardLedgerEntry> {
+    entries.iter_mut().find(|entry| {
+        entry.source_kind == source_kind
+            && entry.source_id == source_id
+            && entry.reward_kind == reward_kind
+    })
+}
+
+/// Records a payout unless one is already active for the same key.
+/// Revoked entries can be claimed again, so a clawed-back reward is paid at most once net.
+fn claim_entry(
+    entries: &mut Vec<RewardLedgerEntry>,
+    source_kind: &str,
+    source_id: &str,
+    reward_kind: &str,
+    amount: u32,
+    now: &str,
+) -> bool {
+    if let Some(entry) = find_entry(entries, source_kind, source_id, reward_kind) {
+        if entry.revoked_at.is_none() {
+            return false;
+        }
+        entry.amount = amount;
+        entry.granted_at = now.to_string();
+        entry.revoked_at = None;
+        return true;
+    }
+
+    entries.push(RewardLedgerEntry {
+        source_kind: source_kind.to_string(),
+        source_id: source_id.to_string(),
+        reward_kind: reward_kind.to_string(),
+        amount,
+        granted_at: now.to_string(),
+        revoked_at: None,
+    });
+    true
+}
+
+/// Keeps the first entry for each key, the one `find_entry` would match.
+pub(crate) fn dedupe_ledger(entries: &mut Vec<RewardLedgerEntry>) {
+    let mut seen = std::collections::HashSet::new();
+    entries.retain(|entry| {
+        seen.insert((
+            entry.source_kind.clone(),
+            entry.source_id.clone(),
+            entry.reward_kind.clone(),
+        ))
+    });
+}
+
+/// Marks an active payout as revoked and returns the entry that was clawed back.
+fn revoke_entry(
+    entries: &mut [RewardLedgerEntry],
+    source_kind: &str,
+    source_id: &str,
+    reward_kind: &str,
+    now: &str,
+) -> Option<RewardLedgerEntry> {
+    let entry = find_entry(entries, source_kind, source_id, reward_kind)?;
+    if entry.revoked_at.is_some() {
+        return None;
+    }
+    entry.revoked_at = Some(now.to_string());
+    Some(entry.clone())
+}
+
+pub fn load_ledger(app: &AppHandle) -> Result<Vec<RewardLedgerEntry>, String> {
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    Ok(store
+        .get(REWARD_LEDGER_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+fn save_ledger(app: &AppHandle, entries: &[RewardLedgerEntry]) -> Result<(), String> {
+    let store = app.store("store.json").map_err(|e| e.to_string())?;
+    store.set(REWARD_LEDGER_KEY, json!(entries));
+    Ok(())
+}
+
+/// Returns `true` when the caller should pay out, `false` when this reward was already granted.
+pub fn claim(
+    app: &AppHandle,
+    source_kind: &str,
+    source_id: &str,
+    reward_kind: &str,
+    amount: u32,
+) -> Result<bool, String> {
+    let claimed = claim_many(
+        app,
+        source_kind,
+        reward_kind,
+        &[(source_id.to_string(), amount)],
+    )?;
+    Ok(claimed[0])
+}
+
+/// Like `claim` for several `(source_id, amount)` pairs, loading and saving the ledger once.
+/// Returns whether each one should pay out, in order.
+pub fn claim_many(
+    app: &AppHandle,
+    source_kind: &str,
+    reward_kind: &str,
+    claims: &[(String, u32)],
+) -> Result<Vec<bool>, String> {
+    let mut entries = load_ledger(app)?;
+    let now = chrono::Utc::now().to_rfc3339();
+    let claimed: Vec<bool> = claims
+        .iter()
+        .map(|(source_id, amount)| {
+            claim_entry(
+                &mut entries,
+                source_kind,
+                source_id,
+                reward_kind,
+                *amount,
+                &now,
+            )
+        })
+        .collect();
+    if claimed.contains(&true) {
+        save_ledger(app, &entries)?;
+    }
+    Ok(claimed)
+}
+
+/// Returns the revoked entry when an active payout existed, so the caller can undo it.
+pub fn revoke(
+    app: &AppHandle,
+    source_kind: &str,
+    source_id: &str,
+    reward_kind: &str,
+) -> Result<Option<RewardLedgerEntry>, String> {
+    let mut entries = load_ledger(app)?;
+    let now = chrono::Utc::now().to_rfc3339();
+    let revoked = revoke_entry(&mut entries, source_kind, source_id, reward_kind, &now);
+    if revoked.is_some() {
+        save_ledger(app, &entries)?;
+    }
+    Ok(revoked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: &str = "2026-01-01T00:00:00Z";
+    const LATER: &str = "2026-01-01T01:00:00Z";
+
+    #[test]
+    fn claim_entry_pays_once_per_key() {
+        let mut entries = Vec::new();
+        assert!(claim_entry(
+            &mut entries,
+            SOURCE_TASK,
+            "t1",
+            REWARD_XP,
+            10,
+            NOW
+        ));
+        assert!(!claim_entry(
+            &mut entries,
+            SOURCE_TASK,
+            "t1",
+            REWARD_XP,
+            10,
+            LATER
+        ));
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].granted_at, NOW);
+    }
+
+    #[test]
+    fn claim_entry_keys_include_reward_kind() {
+        let mut entries = Vec::new();
+        assert!(claim_entry(
+            &mut entries,
+            SOURCE_SESSION,
+            "s1",
+            REWARD_XP,
+            25,
+            NOW
+        ));
+        assert!(claim_entry(
+            &mut entries,
+            SOURCE_SESSION,
+            "s1",
+            REWARD_COINS,
+            10,
+            NOW
+        ));
+        assert!(claim_entry(
+            &mut entries,
+            SOURCE_TASK,
+            "s1",
+            REWARD_XP,
+            10,
+            NOW
+        ));
+        assert_eq!(entries.len(), 3);
+    }
+
+    #[test]
+    fn revoke_entry_allows_single_reclaim() {
+        let mut entries = Vec::new();
+        claim_entry(&mut entries, SOURCE_TASK, "t1", REWARD_XP, 10, NOW);
+
+        let revoked = revoke_entry(&mut entries, SOURCE_TASK, "t1", REWARD_XP, LATER).unwrap();
+        assert_eq!(revoked.amount, 10);
+        assert!(revoke_entry(&mut entries, SOURCE_TASK, "t1", REWARD_XP, LATER).is_none());
+
+        assert!(claim_entry(
+            &mut entries,
+            SOURCE_TASK,
+            "t1",
+            REWARD_XP,
+            10,
+            LATER
+        ));
+        assert!(!claim_entry(
+            &mut entries,
+            SOURCE_TASK,
+            "t1",
+            REWARD_XP,
+            10,
+            LATER
+        ));
+        assert!(entries[0].revoked_at.is_none());
+    }
+
+    #[test]
+    fn revoke_entry_ignores_unknown_key() {
+        let mut entries = Vec::new();
+        assert!(revoke_entry(&mut entries, SOURCE_QUEST, "q1", REWARD_COINS, NOW).is_none());
+    }
+
+    #[test]
+    fn old_keys_stay_claimed_as_the_ledger_grows() {
+        let mut entries = Vec::new();
+        assert!(claim_entry(
+            &mut entries,
+            SOURCE_LEVEL,
+            "5",
+            REWARD_COINS,
+            50,
+            NOW
+        ));
+        entries.extend((0..25_000).map(|i| RewardLedgerEntry {
+            source_kind: SOURCE_TASK.to_string(),
+            source_id: i.to_string(),
+            reward_kind: REWARD_XP.to_string(),
+            amount: 10,
+            granted_at: NOW.to_string(),
+            revoked_at: None,
+        }));
+        assert!(!claim_entry(
+            &mut entries,
+            SOURCE_LEVEL,
+            "5",
+            REWARD_COINS,
+            50,
+            LATER
+        ));
+    }
+
+    #[test]
+    fn dedupe_ledger_keeps_the_first_entry_per_key() {
+        let mut entries = Vec::new();
+        claim_entry(&mut entries, SOURCE_TASK, "t1", REWARD_XP, 10, NOW);
+        claim_entry(&mut entries, SOURCE_TASK, "t2", REWARD_XP, 10, NOW);
+        let mut duplicate = entries[0].clone();
+        duplicate.amount = 99;
+        entries.push(duplicate);
+
+        dedupe_ledger(&mut entries);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].amount, 10);
+    }
+}
@@ -211,7 +211,7 @@ export function ControlPanel() {
         {tab === "shop" && (
           <ShopPanel
             available={available}
-            ownedAccessories={pet.accessories}
+            ownedAccessories={pet.ownedAccessories}
           />
         )}
         {tab === "stats" && (
@@ -450,7 +450,7 @@ export function CustomizationPanel({
                 uiTheme: settings.uiTheme,
                 petSkin: settings.petSkin,
                 petScene: settings.petScene,
-                accessories: pet.accessories,
+                equipped: pet.equipped,
               });
               setLoadoutName("");
             }}
@@ -1,16 +1,36 @@
-import { SHOP_ITEMS } from "../../lib/constants";
-import type { ShopItemId } from "../../lib/constants";
+import { useEffect, useState } from "react";
+import { shopItemIcon } from "../../lib/constants";
 import { getAccessoryBehaviorProfile } from "../../pets/accessoryBehavior";
-import { invokeMaybe } from "../../lib/tauri";
+import { invokeMaybe, invokeOr } from "../../lib/tauri";
+import type { ShopStockItem } from "../../store/types";
 import { CoinBadge } from "../shared/CoinBadge";
 
 interface Props {
   available: number;
-  ownedAccessories: ShopItemId[];
+  ownedAccessories: string[];
+}
+
+function seasonalPackLabel(packId: string) {
+  return packId
+    .split("-")
+    .map((word) => word.charAt(0).toUpperCase() + word.slice(1))
+    .join(" ");
 }
 
 export function ShopPanel({ available, ownedAccessories }: Props) {
-  const handleBuy = async (itemId: ShopItemId) => {
+  const [stock, setStock] = useState<ShopStockItem[]>([]);
+
+  useEffect(() => {
+    let cancelled = false;
+    invokeOr<ShopStockItem[]>("get_shop_items", undefined, []).then((items) => {
+      if (!cancelled) setStock(items);
+    });
+    return () => {
+      cancelled = true;
+    };
+  }, []);
+
+  const handleBuy = async (itemId: string) => {
     await invokeMaybe("purchase_item", { itemId });
   };
 
@@ -23,9 +43,10 @@ export function ShopPanel({ available, ownedAccessories }: Props) {
         <CoinBadge amount={available} />
       </div>
       <div className="grid grid-cols-2 gap-2">
-        {SHOP_ITEMS.map((item) => {
-          const owned = ownedAccessories.includes(item.id);
-          const canAfford = available >= item.cost;
+        {stock.map((item) => {
+          const owned = item.category === "accessory" && ownedAccessories.includes(item.id);
+          const price = item.saleCost ?? item.cost;
+          const canAfford = available >= price;
           const behaviorProfile = getAccessoryBehaviorProfile(item.id);
           return (
             <div
@@ -40,16 +61,29 @@ export function ShopPanel({ available, ownedAccessories }: Props) {
                   : "var(--border-color)",
               }}
             >
-              <div className="text-2xl mb-1">{item.icon}</div>
+              <div className="text-2xl mb-1">{shopItemIcon(item.id)}</div>
               <div className="text-sm font-medium" style={{ color: "var(--text-color)" }}>{item.name}</div>
-              <div className="text-xs mb-2" style={{ color: "var(--muted-color)" }}>
-                <CoinBadge amount={item.cost} />
+              {item.seasonalPack && (
+                <div className="text-[11px]" style={{ color: "var(--accent-color)" }}>
+                  {seasonalPackLabel(item.seasonalPack)}
+                </div>
+              )}
+              <div
+                className="text-xs mb-2 flex items-center justify-center gap-1"
+                style={{ color: "var(--muted-color)" }}
+              >
+                {item.saleCost !== null && (
+                  <span className="line-through" aria-label="Regular price">
+                    {item.cost}
+                  </span>
+                )}
+                <CoinBadge amount={price} />
               </div>
               <div
                 className="text-[11px] mb-2 leading-snug"
                 style={{ color: "var(--muted-color)", minHeight: "2.2rem" }}
               >
-                {behaviorProfile.shopHint}
+                {behaviorProfile.shopHint || item.description}
               </div>
               {owned ? (
                 <span
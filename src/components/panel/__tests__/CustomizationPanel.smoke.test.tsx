@@ -51,6 +51,9 @@ const pet: PetState = {
   scene: "meadow",
   lastInteraction: null,
   lastCareUpdateAt: new Date().toISOString(),
+  inventory: {},
+  ownedAccessories: [],
+  equipped: {},
 };
 
 describe("CustomizationPanel smoke flow", () => {
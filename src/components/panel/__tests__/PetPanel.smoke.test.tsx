@@ -22,6 +22,9 @@ const pet: PetState = {
   scene: "meadow",
   lastInteraction: null,
   lastCareUpdateAt: new Date().toISOString(),
+  inventory: {},
+  ownedAccessories: [],
+  equipped: {},
 };
 
 describe("PetPanel smoke flow", () => {
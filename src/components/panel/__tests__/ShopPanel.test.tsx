@@ -3,21 +3,70 @@ import { render, screen } from "@testing-library/react";
 import userEvent from "@testing-library/user-event";
 import { ShopPanel } from "../ShopPanel";
 import { invoke } from "@tauri-apps/api/core";
-import { SHOP_ITEMS } from "../../../lib/constants";
+import { SHOP_ITEM_ICONS } from "../../../lib/constants";
 import { getAccessoryBehaviorProfile } from "../../../pets/accessoryBehavior";
+import type { ShopStockItem } from "../../../store/types";
 
-vi.mocked(invoke).mockResolvedValue(undefined);
+function stockItem(overrides: Partial<ShopStockItem>): ShopStockItem {
+  return {
+    id: "party_hat",
+    name: "Party Hat",
+    cost: 30,
+    category: "accessory",
+    rarity: "common",
+    slot: "head",
+    description: "",
+    requiredStage: 0,
+    requiredLevel: 0,
+    species: [],
+    availableFrom: null,
+    availableUntil: null,
+    effects: null,
+    saleCost: null,
+    seasonalPack: null,
+    stockedOn: "2026-01-05",
+    ...overrides,
+  };
+}
+
+const STOCK: ShopStockItem[] = [
+  stockItem({ id: "party_hat", name: "Party Hat", cost: 30 }),
+  stockItem({ id: "apple", name: "Apple", cost: 5, category: "food", slot: null }),
+  stockItem({ id: "scarf", name: "Scarf", cost: 36, slot: "neck", saleCost: 27 }),
+  stockItem({
+    id: "knit_beanie",
+    name: "Knit Beanie",
+    cost: 40,
+    rarity: "rare",
+    seasonalPack: "winter-cozy",
+  }),
+];
 
 describe("ShopPanel", () => {
   beforeEach(() => {
     vi.clearAllMocks();
+    vi.mocked(invoke).mockImplementation(async (command: string) =>
+      command === "get_shop_items" ? STOCK : undefined
+    );
   });
 
-  it("renders all shop items", () => {
+  it("renders today's stock from the backend", async () => {
     render(<ShopPanel available={100} ownedAccessories={[]} />);
-    for (const item of SHOP_ITEMS) {
-      expect(screen.getByText(item.name)).toBeInTheDocument();
+    for (const item of STOCK) {
+      expect(await screen.findByText(item.name)).toBeInTheDocument();
     }
+    expect(invoke).toHaveBeenCalledWith("get_shop_items", undefined);
+  });
+
+  it("shows the sale price next to the regular price", async () => {
+    render(<ShopPanel available={100} ownedAccessories={[]} />);
+    expect(await screen.findByLabelText("Regular price")).toHaveTextContent("36");
+    expect(screen.getByText("27")).toBeInTheDocument();
+  });
+
+  it("labels seasonal pack items", async () => {
+    render(<ShopPanel available={100} ownedAccessories={[]} />);
+    expect(await screen.findByText("Winter Cozy")).toBeInTheDocument();
   });
 
   it("displays user balance", () => {
@@ -27,54 +76,63 @@ describe("ShopPanel", () => {
     expect(balanceSection?.textContent).toContain("42");
   });
 
-  it("shows 'Owned' for purchased accessories", () => {
+  it("shows 'Owned' for purchased accessories", async () => {
     render(<ShopPanel available={100} ownedAccessories={["party_hat"]} />);
-    expect(screen.getByText("Owned")).toBeInTheDocument();
+    expect(await screen.findByText("Owned")).toBeInTheDocument();
   });
 
-  it("shows Buy buttons for unowned items", () => {
+  it("shows Buy buttons for unowned items", async () => {
     render(<ShopPanel available={100} ownedAccessories={[]} />);
-    const buyButtons = screen.getAllByRole("button", { name: "Buy" });
-    expect(buyButtons.length).toBe(SHOP_ITEMS.length);
+    const buyButtons = await screen.findAllByRole("button", { name: "Buy" });
+    expect(buyButtons.length).toBe(STOCK.length);
   });
 
-  it("disables Buy button when insufficient coins", () => {
+  it("disables Buy button when insufficient coins", async () => {
     render(<ShopPanel available={0} ownedAccessories={[]} />);
-    const buyButtons = screen.getAllByRole("button", { name: "Buy" });
+    const buyButtons = await screen.findAllByRole("button", { name: "Buy" });
     for (const btn of buyButtons) {
       expect(btn).toBeDisabled();
     }
   });
 
-  it("enables Buy button when sufficient coins", () => {
+  it("enables Buy button when sufficient coins", async () => {
     // Apple costs 5
     render(<ShopPanel available={5} ownedAccessories={[]} />);
-    const buyButtons = screen.getAllByRole("button", { name: "Buy" });
+    const buyButtons = await screen.findAllByRole("button", { name: "Buy" });
     const enabledButtons = buyButtons.filter((btn) => !btn.hasAttribute("disabled"));
-    expect(enabledButtons.length).toBeGreaterThan(0);
+    expect(enabledButtons.length).toBe(1);
+  });
+
+  it("uses the sale price to decide affordability", async () => {
+    // Scarf costs 36 but is on sale for 27
+    render(<ShopPanel available={27} ownedAccessories={[]} />);
+    const buyButtons = await screen.findAllByRole("button", { name: "Buy" });
+    expect(buyButtons[2]).toBeEnabled();
   });
 
   it("calls invoke purchase_item on Buy click", async () => {
     const user = userEvent.setup();
     render(<ShopPanel available={100} ownedAccessories={[]} />);
 
-    const buyButtons = screen.getAllByRole("button", { name: "Buy" });
+    const buyButtons = await screen.findAllByRole("button", { name: "Buy" });
     await user.click(buyButtons[0]);
 
-    expect(invoke).toHaveBeenCalledWith("purchase_item", { itemId: SHOP_ITEMS[0].id });
+    expect(invoke).toHaveBeenCalledWith("purchase_item", { itemId: STOCK[0].id });
   });
 
-  it("renders item icons", () => {
+  it("renders item icons", async () => {
     render(<ShopPanel available={100} ownedAccessories={[]} />);
-    for (const item of SHOP_ITEMS) {
-      expect(screen.getByText(item.icon)).toBeInTheDocument();
+    for (const item of STOCK) {
+      expect(await screen.findByText(SHOP_ITEM_ICONS[item.id])).toBeInTheDocument();
     }
   });
 
-  it("shows accessory behavior hints", () => {
+  it("shows accessory behavior hints", async () => {
     render(<ShopPanel available={100} ownedAccessories={[]} />);
-    for (const item of SHOP_ITEMS) {
-      expect(screen.getByText(getAccessoryBehaviorProfile(item.id).shopHint)).toBeInTheDocument();
+    for (const item of STOCK) {
+      expect(
+        await screen.findByText(getAccessoryBehaviorProfile(item.id).shopHint)
+      ).toBeInTheDocument();
     }
   });
 });
@@ -4,9 +4,26 @@ import userEvent from "@testing-library/user-event";
 import { TaskList } from "../TaskList";
 import type { Task } from "../../../store/types";
 
+const task = (fields: Pick<Task, "id" | "title" | "completed" | "createdAt">): Task => ({
+  priority: "none",
+  dueDate: null,
+  notes: "",
+  tags: [],
+  completedAt: null,
+  checklist: [],
+  completionRule: "manual",
+  recurrence: null,
+  archivedAt: null,
+  deletedAt: null,
+  seriesId: null,
+  listId: "inbox",
+  sortIndex: 0,
+  ...fields,
+});
+
 const mockTasks: Task[] = [
-  { id: "1", title: "Write tests", completed: false, createdAt: "2025-01-01T00:00:00Z" },
-  { id: "2", title: "Fix bug", completed: true, createdAt: "2025-01-01T01:00:00Z" },
+  task({ id: "1", title: "Write tests", completed: false, createdAt: "2025-01-01T00:00:00Z" }),
+  task({ id: "2", title: "Fix bug", completed: true, createdAt: "2025-01-01T01:00:00Z" }),
 ];
 
 const defaultProps = {
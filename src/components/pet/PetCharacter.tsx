@@ -1,24 +1,28 @@
-import type { ShopItemId } from "../../lib/constants";
+import { shopItemIcon } from "../../lib/constants";
 import { getSpeciesPackById } from "../../pets/species";
 
 interface Props {
   stage: number;
-  accessories: ShopItemId[];
+  accessories: string[];
   speciesId: string;
   speciesMotionClass?: string;
 }
 
-const ACCESSORY_GLYPHS: Record<ShopItemId, string> = {
+/** Glyphs drawn on the pet; items without one reuse their shop icon. */
+const ACCESSORY_GLYPHS: Record<string, string> = {
   party_hat: "🎉",
   bow_tie: "🎀",
   sunglasses: "🕶",
   scarf: "🧣",
   apple: "🍎",
   cookie: "🍪",
+  flower_crown: "🌸",
+  knit_beanie: "🧶",
+  hot_cocoa: "☕",
 };
 
 function anchorForAccessory(
-  accessory: ShopItemId,
+  accessory: string,
   anchors: ReturnType<typeof getSpeciesPackById>["accessoryAnchors"]
 ) {
   if (accessory === "party_hat") return anchors.head;
@@ -52,7 +56,7 @@ export function PetCharacter({ stage, accessories, speciesId, speciesMotionClass
               transform: "translate(-50%, -50%)",
             }}
           >
-            {ACCESSORY_GLYPHS[accessory]}
+            {ACCESSORY_GLYPHS[accessory] ?? shopItemIcon(accessory)}
           </span>
         );
       })}
@@ -26,6 +26,9 @@ export function PetOverlay() {
     scene: "meadow",
     lastInteraction: null,
     lastCareUpdateAt: new Date().toISOString(),
+    inventory: {},
+    ownedAccessories: [],
+    equipped: {},
   });
   const [settings, setSettings] = useState<Pick<
     Settings,
@@ -63,6 +66,9 @@ export function PetOverlay() {
         scene: "meadow",
         lastInteraction: null,
         lastCareUpdateAt: new Date().toISOString(),
+        inventory: {},
+        ownedAccessories: [],
+        equipped: {},
       }
     ).then(setPet);
 
@@ -1,7 +1,5 @@
-import type { ShopItemId } from "../../lib/constants";
-
 interface Props {
-  accessories: ShopItemId[];
+  accessories: string[];
 }
 
 export function PetStage2({ accessories }: Props) {
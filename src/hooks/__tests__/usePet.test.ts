@@ -58,6 +58,9 @@ vi.mock("../../lib/tauri", () => ({
         scene: "meadow",
         lastInteraction: null,
         lastCareUpdateAt: new Date().toISOString(),
+        inventory: {},
+        ownedAccessories: [],
+        equipped: {},
       };
     }
     return defaultValue;
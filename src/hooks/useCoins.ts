@@ -1,6 +1,6 @@
-import { useState, useEffect, useCallback } from "react";
+import { useState, useEffect } from "react";
 import { EVENT_COINS_CHANGED } from "../lib/events";
-import { invokeMaybe, invokeOr, listenSafe } from "../lib/tauri";
+import { invokeOr, listenSafe } from "../lib/tauri";
 import type { CoinBalance } from "../store/types";
 
 export function useCoins() {
@@ -27,12 +27,5 @@ export function useCoins() {
     };
   }, []);
 
-  const spend = useCallback(async (amount: number) => {
-    const updated = await invokeMaybe<CoinBalance>("spend_coins", { amount });
-    if (!updated) return coins;
-    setCoins(updated);
-    return updated;
-  }, [coins]);
-
-  return { coins, available: coins.total - coins.spent, spend };
+  return { coins, available: coins.total - coins.spent };
 }
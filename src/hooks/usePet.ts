@@ -23,6 +23,9 @@ const defaultPet: PetState = {
   scene: "meadow",
   lastInteraction: null,
   lastCareUpdateAt: new Date().toISOString(),
+  inventory: {},
+  ownedAccessories: [],
+  equipped: {},
 };
 
 export function usePet() {
@@ -73,6 +76,23 @@ export function usePet() {
     return updated;
   }, []);
 
+  const feedItem = useCallback(async (itemId: string) => {
+    const updated = await invokeMaybe<PetState>("feed_item", { itemId });
+    if (!updated) return null;
+    setPet(updated);
+    return updated;
+  }, []);
+
+  const equipAccessory = useCallback(async (itemId: string, equipped: boolean) => {
+    const updated = await invokeMaybe<PetState>(
+      equipped ? "equip_accessory" : "unequip_accessory",
+      { itemId },
+    );
+    if (!updated) return null;
+    setPet(updated);
+    return updated;
+  }, []);
+
   const setCustomization = useCallback(async (skin?: string, scene?: string) => {
     const updated = await invokeMaybe<PetState>("set_pet_customization", { skin, scene });
     if (!updated) return null;
@@ -98,6 +118,8 @@ export function usePet() {
     stageProgress,
     stageSpan,
     interact,
+    feedItem,
+    equipAccessory,
     setCustomization,
     setSpecies,
   };
@@ -44,6 +44,15 @@ export function usePetEvents() {
     return event;
   }, [refresh]);
 
+  const rerollQuest = useCallback(async () => {
+    const quest = await invokeMaybe<PetQuest>("reroll_quest");
+    if (!quest) return null;
+    if (mounted.current) {
+      refresh();
+    }
+    return quest;
+  }, [refresh]);
+
   const resolveEvent = useCallback(async (eventId: string) => {
     const updated = await invokeMaybe<PetEvent[]>("resolve_pet_event", { eventId });
     if (!updated) return events;
@@ -51,5 +60,5 @@ export function usePetEvents() {
     return updated;
   }, [events]);
 
-  return { events, activeQuest, rollFeedback, refresh, rollEvent, resolveEvent };
+  return { events, activeQuest, rollFeedback, refresh, rollEvent, rerollQuest, resolveEvent };
 }
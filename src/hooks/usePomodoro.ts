@@ -225,8 +225,12 @@ export function usePomodoro() {
               sessionsCompleted: prev.sessionsCompleted + 1,
             };
           } else if (prev.phase === "break") {
-            // Break complete → idle
-            invokeQuiet("set_pet_animation", { animation: "idle" });
+            // Break ran its full length → idle (Rust credits the break goal + energy)
+            if (prev.sessionId) {
+              invokeQuiet("complete_break", { sessionId: prev.sessionId });
+            } else {
+              invokeQuiet("set_pet_animation", { animation: "idle" });
+            }
             const p = TIMER_PRESETS[prev.preset];
             return {
               ...prev,
@@ -250,11 +254,6 @@ export function usePomodoro() {
     const timeout = setTimeout(() => {
       const p = TIMER_PRESETS[state.preset];
       invokeQuiet("set_pet_animation", { animation: "break" });
-      // Track break goal (absolute count — each completed work session starts a break)
-      invokeQuiet("update_goal_progress", {
-        goalId: "breaks",
-        progress: state.sessionsCompleted,
-      });
       setState((s) => ({
         ...s,
         phase: "break",
@@ -263,7 +262,7 @@ export function usePomodoro() {
       }));
     }, 3000);
     return () => clearTimeout(timeout);
-  }, [state.phase, state.preset, state.sessionsCompleted]);
+  }, [state.phase, state.preset]);
 
   useEffect(() => {
     if (!hydrated) return;
@@ -4,7 +4,9 @@ import {
   DEFAULT_PRESET,
   COINS_PER_POMODORO,
   EVOLUTION_THRESHOLDS,
-  SHOP_ITEMS,
+  SHOP_ITEM_ICONS,
+  DEFAULT_SHOP_ITEM_ICON,
+  shopItemIcon,
   ANIMATION_STATES,
   DEFAULT_DAILY_GOALS,
 } from "../constants";
@@ -68,33 +70,16 @@ describe("EVOLUTION_THRESHOLDS", () => {
   });
 });
 
-describe("SHOP_ITEMS", () => {
-  it("has 6 items", () => {
-    expect(SHOP_ITEMS).toHaveLength(6);
-  });
-
-  it("all items have required fields", () => {
-    for (const item of SHOP_ITEMS) {
-      expect(item.id).toBeTruthy();
-      expect(item.name).toBeTruthy();
-      expect(item.cost).toBeGreaterThan(0);
-      expect(item.icon).toBeTruthy();
+describe("SHOP_ITEM_ICONS", () => {
+  it("has an icon for every catalog item", () => {
+    expect(Object.keys(SHOP_ITEM_ICONS)).toHaveLength(9);
+    for (const icon of Object.values(SHOP_ITEM_ICONS)) {
+      expect(icon).toBeTruthy();
     }
   });
-
-  it("has no duplicate IDs", () => {
-    const ids = SHOP_ITEMS.map((i) => i.id);
-    expect(new Set(ids).size).toBe(ids.length);
-  });
-
-  it("contains expected items", () => {
-    const ids = SHOP_ITEMS.map((i) => i.id);
-    expect(ids).toContain("party_hat");
-    expect(ids).toContain("bow_tie");
-    expect(ids).toContain("sunglasses");
-    expect(ids).toContain("scarf");
-    expect(ids).toContain("apple");
-    expect(ids).toContain("cookie");
+  it("falls back to a generic icon for unknown items", () => {
+    expect(shopItemIcon("scarf")).toBe(SHOP_ITEM_ICONS.scarf);
+    expect(shopItemIcon("future_item")).toBe(DEFAULT_SHOP_ITEM_ICON);
   });
 });
 
@@ -82,6 +82,9 @@ describe("photo booth", () => {
       scene: "meadow",
       lastInteraction: null,
       lastCareUpdateAt: new Date().toISOString(),
+      inventory: {},
+      ownedAccessories: [],
+      equipped: {},
     };
     const settings: Settings = {
       timerPreset: "standard",
@@ -152,6 +155,9 @@ describe("photo booth", () => {
       scene: "cozy_room",
       lastInteraction: null,
       lastCareUpdateAt: new Date().toISOString(),
+      inventory: {},
+      ownedAccessories: [],
+      equipped: {},
     };
     const settings: Settings = {
       timerPreset: "standard",
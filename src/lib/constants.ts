@@ -15,16 +15,25 @@ export const EVOLUTION_THRESHOLDS = {
   stage2: 15,
 } as const;
 
-export const SHOP_ITEMS = [
-  { id: "party_hat", name: "Party Hat", cost: 30, icon: "🎩" },
-  { id: "bow_tie", name: "Bow Tie", cost: 20, icon: "🎀" },
-  { id: "sunglasses", name: "Sunglasses", cost: 25, icon: "🕶️" },
-  { id: "scarf", name: "Scarf", cost: 35, icon: "🧣" },
-  { id: "apple", name: "Apple", cost: 5, icon: "🍎" },
-  { id: "cookie", name: "Cookie", cost: 10, icon: "🍪" },
-] as const;
-
-export type ShopItemId = (typeof SHOP_ITEMS)[number]["id"];
+/** Item ids in the bundled shop catalog; names and prices come from `get_shop_items`. */
+/** Icons for known catalog items; ids come from `get_shop_items`, so any may be missing. */
+export const SHOP_ITEM_ICONS: Record<string, string> = {
+  party_hat: "🎩",
+  bow_tie: "🎀",
+  sunglasses: "🕶️",
+  scarf: "🧣",
+  apple: "🍎",
+  cookie: "🍪",
+  flower_crown: "🌸",
+  knit_beanie: "🧶",
+  hot_cocoa: "☕",
+};
+
+export const DEFAULT_SHOP_ITEM_ICON = "🛍️";
+
+export function shopItemIcon(itemId: string) {
+  return SHOP_ITEM_ICONS[itemId] ?? DEFAULT_SHOP_ITEM_ICON;
+}
 
 export const ANIMATION_STATES = [
   "idle",
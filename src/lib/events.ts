@@ -4,6 +4,7 @@ export const EVENT_GOALS_CHANGED = "goals-changed";
 export const EVENT_SETTINGS_CHANGED = "settings-changed";
 export const EVENT_TIMER_RUNTIME_CHANGED = "timer-runtime-changed";
 export const EVENT_PROFILE_CHANGED = "profile-changed";
+export const EVENT_LEVEL_UP = "level-up";
 export const EVENT_ANALYTICS_CHANGED = "analytics-changed";
 export const EVENT_FOCUS_GUARDRAILS_ALERT = "focus-guardrails-alert";
 
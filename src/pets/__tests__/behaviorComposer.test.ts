@@ -98,4 +98,19 @@ describe("behavior composer", () => {
 
     expect(result.accessoryClasses).toContain("quest-accent-scarf");
   });
+
+  it("ignores accessories it has no profile for", () => {
+    const result = composePetBehavior({
+      species: getSpeciesPackById("corgi"),
+      accessories: ["future_item"],
+      lastInteraction: null,
+      animationState: "idle",
+      animationBudget: "high",
+      quietModeEnabled: false,
+      focusModeEnabled: false,
+      contextChilled: false,
+    });
+
+    expect(result.accessoryClasses).toBe("");
+  });
 });
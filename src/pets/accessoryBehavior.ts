@@ -1,7 +1,5 @@
-import type { ShopItemId } from "../lib/constants";
-
 export interface AccessoryBehaviorProfile {
-  id: ShopItemId;
+  id: string;
   behaviorClass: string | null;
   questAccentClass: string | null;
   minMotionLevel: 0 | 1 | 2 | 3;
@@ -10,7 +8,7 @@ export interface AccessoryBehaviorProfile {
   shopHint: string;
 }
 
-export const ACCESSORY_BEHAVIOR_PROFILES: Record<ShopItemId, AccessoryBehaviorProfile> = {
+export const ACCESSORY_BEHAVIOR_PROFILES: Record<string, AccessoryBehaviorProfile> = {
   party_hat: {
     id: "party_hat",
     behaviorClass: "behavior-partyhat",
@@ -65,8 +63,46 @@ export const ACCESSORY_BEHAVIOR_PROFILES: Record<ShopItemId, AccessoryBehaviorPr
     motionBoost: 0,
     shopHint: "Adds a gentle cookie sparkle with active idle behavior.",
   },
+  flower_crown: {
+    id: "flower_crown",
+    behaviorClass: null,
+    questAccentClass: null,
+    minMotionLevel: 0,
+    allowWhenChilled: true,
+    motionBoost: 0,
+    shopHint: "A seasonal crown that stays calm in every mode.",
+  },
+  knit_beanie: {
+    id: "knit_beanie",
+    behaviorClass: null,
+    questAccentClass: null,
+    minMotionLevel: 0,
+    allowWhenChilled: true,
+    motionBoost: 0,
+    shopHint: "A seasonal beanie that stays calm in every mode.",
+  },
+  hot_cocoa: {
+    id: "hot_cocoa",
+    behaviorClass: null,
+    questAccentClass: "quest-accent-snack",
+    minMotionLevel: 0,
+    allowWhenChilled: true,
+    motionBoost: 0,
+    shopHint: "A warm seasonal treat for the pantry.",
+  },
 };
 
-export function getAccessoryBehaviorProfile(accessoryId: ShopItemId) {
-  return ACCESSORY_BEHAVIOR_PROFILES[accessoryId];
+/** Unknown catalog items stay still and fall back to their shop description. */
+export function getAccessoryBehaviorProfile(accessoryId: string): AccessoryBehaviorProfile {
+  return (
+    ACCESSORY_BEHAVIOR_PROFILES[accessoryId] ?? {
+      id: accessoryId,
+      behaviorClass: null,
+      questAccentClass: null,
+      minMotionLevel: 0,
+      allowWhenChilled: true,
+      motionBoost: 0,
+      shopHint: "",
+    }
+  );
 }
@@ -1,12 +1,12 @@
-import type { AnimationState, ShopItemId } from "../lib/constants";
-import { ACCESSORY_BEHAVIOR_PROFILES } from "./accessoryBehavior";
+import type { AnimationState } from "../lib/constants";
+import { getAccessoryBehaviorProfile } from "./accessoryBehavior";
 import type { PetSpeciesPack } from "./species";
 
 type AnimationBudget = "low" | "medium" | "high";
 
 interface ComposePetBehaviorInput {
   species: PetSpeciesPack;
-  accessories: ShopItemId[];
+  accessories: string[];
   lastInteraction: string | null;
   animationState: AnimationState;
   animationBudget: AnimationBudget;
@@ -71,7 +71,7 @@ function shouldPreserveAnimationState(animationState: AnimationState) {
 }
 
 function buildAccessoryClasses(
-  accessories: ShopItemId[],
+  accessories: string[],
   shouldChill: boolean,
   motionLevel: 0 | 1 | 2 | 3,
   lastInteraction: string | null
@@ -79,8 +79,8 @@ function buildAccessoryClasses(
   const classes = new Set<string>();
   let questAccent: string | null = null;
   for (const accessory of accessories) {
-    const profile = ACCESSORY_BEHAVIOR_PROFILES[accessory];
-    if (!profile || !profile.behaviorClass) {
+    const profile = getAccessoryBehaviorProfile(accessory);
+    if (!profile.behaviorClass) {
       continue;
     }
     if (shouldChill && !profile.allowWhenChilled) {
@@ -110,7 +110,7 @@ export function composePetBehavior(input: ComposePetBehaviorInput): PetBehaviorC
 
   let motionLevel = baselineMotionLevel(input.species);
   for (const accessory of input.accessories) {
-    motionLevel = clampMotionLevel(motionLevel + ACCESSORY_BEHAVIOR_PROFILES[accessory].motionBoost);
+    motionLevel = clampMotionLevel(motionLevel + getAccessoryBehaviorProfile(accessory).motionBoost);
   }
 
   // Precedence order: species baseline -> accessory modifiers -> chill clamp -> budget clamp.
@@ -1,9 +1,9 @@
-import type { AnimationState, ShopItemId, TimerPreset } from "../lib/constants";
+import type { AnimationState, TimerPreset } from "../lib/constants";
 
 export interface PetState {
   currentStage: number;
   animationState: AnimationState;
-  accessories: ShopItemId[];
+  accessories: string[];
   totalPomodoros: number;
   speciesId: string;
   evolutionThresholds: number[];
@@ -18,6 +18,12 @@ export interface PetState {
   scene: string;
   lastInteraction: string | null;
   lastCareUpdateAt: string;
+  /** Units of consumable food by shop item id. */
+  inventory: Record<string, number>;
+  /** Accessories bought in the shop; `accessories` lists the ones being worn. */
+  ownedAccessories: string[];
+  /** Accessory worn in each slot (`head`, `face`, `neck`, `held`). */
+  equipped: Record<string, string>;
 }
 
 export interface PomodoroSession {
@@ -28,16 +34,74 @@ export interface PomodoroSession {
   breakDuration: number;
 }
 
+/** A catalog item in today's shop stock, as returned by `get_shop_items`. */
+export interface ShopStockItem {
+  id: string;
+  name: string;
+  cost: number;
+  category: "accessory" | "food";
+  rarity: string;
+  slot: string | null;
+  description: string;
+  requiredStage: number;
+  requiredLevel: number;
+  species: string[];
+  availableFrom: string | null;
+  availableUntil: string | null;
+  effects: { hunger: number; affection: number; energy: number } | null;
+  /** Discounted price when the item is today's deal. */
+  saleCost: number | null;
+  /** Seasonal pack the item is sold through, if any. */
+  seasonalPack: string | null;
+  stockedOn: string;
+}
+
 export interface CoinBalance {
   total: number;
   spent: number;
 }
 
+export interface ChecklistItem {
+  id: string;
+  title: string;
+  completed: boolean;
+  completedAt: string | null;
+}
+
+/** `weekdays` uses 0 = Monday; `intervalDays` applies to the "interval" frequency. */
+export interface TaskRecurrence {
+  frequency: "daily" | "weekdays" | "weekly" | "interval";
+  weekdays: number[];
+  intervalDays: number;
+}
+
+export interface TaskList {
+  id: string;
+  name: string;
+  createdAt: string;
+}
+
 export interface Task {
   id: string;
   title: string;
   completed: boolean;
   createdAt: string;
+  priority: "none" | "low" | "medium" | "high";
+  /** Local date, `YYYY-MM-DD`. */
+  dueDate: string | null;
+  notes: string;
+  tags: string[];
+  completedAt: string | null;
+  checklist: ChecklistItem[];
+  completionRule: "manual" | "auto";
+  recurrence: TaskRecurrence | null;
+  archivedAt: string | null;
+  /** Set while the task is in the trash. */
+  deletedAt: string | null;
+  /** Shared by every instance generated from the same recurring task. */
+  seriesId: string | null;
+  listId: string;
+  sortIndex: number;
 }
 
 export interface DailyGoal {
@@ -143,7 +207,9 @@ export interface CustomizationLoadout {
   uiTheme: string;
   petSkin: string;
   petScene: string;
-  accessories: string[];
+  /** Flat list from loadouts saved before accessory slots. */
+  accessories?: string[];
+  equipped: Record<string, string>;
 }
 
 export interface FocusGuardrailsStatus {